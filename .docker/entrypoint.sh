@@ -1,7 +1,5 @@
 #!/bin/bash
 set -euo pipefail
 
-if [[ -f "src/bin/day-${1}.rs" ]]; then
-    cargo build --release --bin "day-${1}"
-    time "target/release/day-${1}" "inputs/${1}"
-fi
+cargo build --release --bin aoc
+time target/release/aoc run "${@}"
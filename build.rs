@@ -0,0 +1,79 @@
+//! Generates a `#[test]` per input that has expected answers, for `solution_tests!` to
+//! include. `inputs/NN` pairs with `answers/NN`, and `inputs/extra/NN.name` with
+//! `inputs/extra/NN.name.answer`.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn test_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("case_{}", name)
+    } else {
+        name
+    }
+}
+
+/// (test name, test case expression) for each case of a day. Mirrors `verify::cases`,
+/// which the build script can't use.
+fn cases(day: &str) -> Vec<(String, String)> {
+    let mut cases = Vec::new();
+    let input = format!("inputs/{}", day);
+    let answers = format!("answers/{}", day);
+    if Path::new(&input).is_file() && Path::new(&answers).is_file() {
+        cases.push((
+            "input".to_string(),
+            format!("TestCase::from_answers({:?}, {:?})", input, answers),
+        ));
+    }
+
+    let mut extras = fs::read_dir("inputs/extra")
+        .map(|dir| {
+            dir.filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    extras.sort();
+    let prefix = format!("{}.", day);
+    for file in extras {
+        let name = match file.strip_prefix(&prefix) {
+            Some(name) if !name.ends_with(".answer") => name,
+            _ => continue,
+        };
+        let input = format!("inputs/extra/{}", file);
+        if Path::new(&format!("{}.answer", input)).is_file() {
+            cases.push((
+                test_name(name),
+                format!("TestCase::from_sidecar({:?})", input),
+            ));
+        }
+    }
+    cases
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=inputs");
+    println!("cargo:rerun-if-changed=answers");
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("cases");
+    fs::create_dir_all(&out).unwrap();
+    for day in 1..=25 {
+        let day = format!("{:02}", day);
+        let mut code = String::new();
+        for (name, case) in cases(&day) {
+            writeln!(
+                code,
+                "#[test]\nfn {}() {{\n    crate::commons::test_helpers::{}\n        .slow_part2(SLOW_PART2.contains(&{:?}))\n        .check::<Solver>();\n}}",
+                name, case, name
+            )
+            .unwrap();
+        }
+        fs::write(out.join(format!("day{}.rs", day)), code).unwrap();
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use aoc2022::days::day16::Day16;
+use aoc2022::solution::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = Day16::parse(input);
+});
@@ -0,0 +1,244 @@
+use crate::error::AocError;
+use crate::solution::Day;
+use std::any::Any;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "Need at least one sample");
+        samples.sort();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DayTimings {
+    pub day: u8,
+    pub runs: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayTimings {
+    pub fn phases(&self) -> [(&'static str, &Stats); 3] {
+        [
+            ("parse", &self.parse),
+            ("part1", &self.part1),
+            ("part2", &self.part2),
+        ]
+    }
+
+    pub fn total_median(&self) -> Duration {
+        self.parse.median + self.part1.median + self.part2.median
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn bench(
+    day: &Day,
+    input: &str,
+    params: &dyn Any,
+    runs: usize,
+) -> Result<DayTimings, AocError> {
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+
+    for _ in 0..runs.max(1) {
+        let (parsed, elapsed) = time(|| day.parse(input));
+        let parsed = parsed?;
+        parse.push(elapsed);
+        let (answer, elapsed) = time(|| day.part1(parsed.as_ref(), params));
+        answer?;
+        part1.push(elapsed);
+        let (answer, elapsed) = time(|| day.part2(parsed.as_ref(), params));
+        answer?;
+        part2.push(elapsed);
+    }
+
+    Ok(DayTimings {
+        day: day.number(),
+        runs: parse.len(),
+        parse: Stats::from_samples(&mut parse),
+        part1: Stats::from_samples(&mut part1),
+        part2: Stats::from_samples(&mut part2),
+    })
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Error, Debug)]
+#[error("Unknown format: {0}")]
+pub struct UnknownFormat(String);
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => Err(UnknownFormat(s.to_string())),
+        }
+    }
+}
+
+fn millis(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+pub fn render(timings: &[DayTimings], format: Format) -> String {
+    match format {
+        Format::Table => render_table(timings),
+        Format::Json => render_json(timings),
+        Format::Csv => render_csv(timings),
+    }
+}
+
+fn render_table(timings: &[DayTimings]) -> String {
+    let mut s = String::new();
+    writeln!(
+        s,
+        "{:<4} {:<6} {:>12} {:>12} {:>12}",
+        "Day", "Phase", "Min (ms)", "Median (ms)", "Max (ms)"
+    )
+    .unwrap();
+    for t in timings {
+        for (phase, stats) in t.phases() {
+            writeln!(
+                s,
+                "{:<4} {:<6} {:>12.3} {:>12.3} {:>12.3}",
+                format!("{:02}", t.day),
+                phase,
+                millis(stats.min),
+                millis(stats.median),
+                millis(stats.max)
+            )
+            .unwrap();
+        }
+    }
+    let total = timings.iter().map(|t| t.total_median()).sum::<Duration>();
+    writeln!(s, "Total of medians: {:.3} ms", millis(total)).unwrap();
+    s
+}
+
+fn render_json(timings: &[DayTimings]) -> String {
+    let days = timings
+        .iter()
+        .map(|t| {
+            let phases = t
+                .phases()
+                .iter()
+                .map(|(phase, stats)| {
+                    format!(
+                        "\"{}\":{{\"min_ns\":{},\"median_ns\":{},\"max_ns\":{}}}",
+                        phase,
+                        stats.min.as_nanos(),
+                        stats.median.as_nanos(),
+                        stats.max.as_nanos()
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(",");
+            format!("{{\"day\":{},\"runs\":{},{}}}", t.day, t.runs, phases)
+        })
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("[{}]\n", days)
+}
+
+fn render_csv(timings: &[DayTimings]) -> String {
+    let mut s = String::from("day,phase,runs,min_ns,median_ns,max_ns\n");
+    for t in timings {
+        for (phase, stats) in t.phases() {
+            writeln!(
+                s,
+                "{},{},{},{},{},{}",
+                t.day,
+                phase,
+                t.runs,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.max.as_nanos()
+            )
+            .unwrap();
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let mut samples = [5, 1, 3].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+
+        let mut samples = [4, 1, 2, 8].map(Duration::from_millis);
+        assert_eq!(
+            Stats::from_samples(&mut samples).median,
+            Duration::from_millis(3)
+        );
+    }
+
+    #[test]
+    fn machine_readable() {
+        let stats = Stats {
+            min: Duration::from_nanos(1),
+            median: Duration::from_nanos(2),
+            max: Duration::from_nanos(3),
+        };
+        let timings = [DayTimings {
+            day: 7,
+            runs: 3,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        }];
+
+        assert_eq!(
+            render(&timings, Format::Csv).lines().nth(1),
+            Some("7,parse,3,1,2,3")
+        );
+        assert!(render(&timings, Format::Json)
+            .starts_with("[{\"day\":7,\"runs\":3,\"parse\":{\"min_ns\":1,"));
+    }
+}
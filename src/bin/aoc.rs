@@ -0,0 +1,413 @@
+use aoc2022::bench::{self, Format};
+use aoc2022::commons::io::read_input;
+use aoc2022::days;
+use aoc2022::fetch::{Fetcher, Outcome};
+use aoc2022::params::{self, ParamError};
+use aoc2022::scaffold;
+use aoc2022::solution::Day;
+use aoc2022::verify::{self, Case, Status};
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+use thiserror::Error;
+
+const USAGE: &str = "Usage:
+    aoc list
+    aoc run <day|all> [--part <1|2>] [--input <path>] [--param <name=value>]...
+    aoc verify [day|all]
+    aoc bench <day|all> [--runs <n>] [--format <table|json|csv>]
+    aoc fetch <day|all> [--refresh] [--base-url <url>]
+    aoc new <day>";
+
+#[derive(Error, Debug)]
+enum UsageError {
+    #[error("Missing argument: {0}")]
+    MissingArgument(&'static str),
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+    #[error("Unknown option: {0}")]
+    UnknownOption(String),
+    #[error("Unknown day: {0}")]
+    UnknownDay(String),
+    #[error("Bad part: {0}")]
+    BadPart(String),
+    #[error("Bad run count: {0}")]
+    BadRuns(String),
+    #[error(transparent)]
+    BadFormat(#[from] bench::UnknownFormat),
+    #[error("--input can only be used with a single day")]
+    InputWithAll,
+    #[error("--param can only be used with a single day")]
+    ParamWithAll,
+    #[error(transparent)]
+    BadParam(#[from] ParamError),
+}
+
+#[derive(Error, Debug)]
+#[error("{0} check(s) failed")]
+struct VerifyFailed(usize);
+
+#[derive(Error, Debug)]
+#[error("{0} day(s) failed")]
+struct RunFailed(usize);
+
+enum Selection {
+    All,
+    Single(Day),
+}
+
+impl Selection {
+    fn parse(day: String) -> Result<Self, UsageError> {
+        if day == "all" {
+            Ok(Selection::All)
+        } else {
+            let number = day
+                .parse()
+                .map_err(|_| UsageError::UnknownDay(day.clone()))?;
+            Ok(Selection::Single(
+                days::get(number).ok_or(UsageError::UnknownDay(day))?,
+            ))
+        }
+    }
+}
+
+struct RunArgs {
+    selection: Selection,
+    part: Option<u8>,
+    input: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl RunArgs {
+    fn parse(args: &mut impl Iterator<Item = String>) -> Result<Self, UsageError> {
+        let day = args.next().ok_or(UsageError::MissingArgument("day"))?;
+        let selection = Selection::parse(day)?;
+
+        let mut part = None;
+        let mut input = None;
+        let mut params = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--part" => {
+                    let p = args.next().ok_or(UsageError::MissingArgument("part"))?;
+                    part = match p.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(UsageError::BadPart(p)),
+                    };
+                }
+                "--input" => {
+                    input = Some(args.next().ok_or(UsageError::MissingArgument("input"))?);
+                }
+                "--param" => {
+                    let p = args.next().ok_or(UsageError::MissingArgument("param"))?;
+                    params.push(params::parse_override(&p)?);
+                }
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        if matches!(selection, Selection::All) {
+            if input.is_some() {
+                return Err(UsageError::InputWithAll);
+            }
+            if !params.is_empty() {
+                return Err(UsageError::ParamWithAll);
+            }
+        }
+
+        Ok(RunArgs {
+            selection,
+            part,
+            input,
+            params,
+        })
+    }
+}
+
+struct FetchArgs {
+    days: Vec<u8>,
+    refresh: bool,
+    base_url: Option<String>,
+}
+
+impl FetchArgs {
+    fn parse(args: &mut impl Iterator<Item = String>) -> Result<Self, UsageError> {
+        let day = args.next().ok_or(UsageError::MissingArgument("day"))?;
+        // Inputs can be fetched ahead of writing the solver, so any puzzle day is fine
+        let days = if day == "all" {
+            days::all().iter().map(|d| d.number()).collect()
+        } else {
+            match day.parse() {
+                Ok(n) if (1..=25).contains(&n) => vec![n],
+                _ => return Err(UsageError::UnknownDay(day)),
+            }
+        };
+
+        let mut refresh = false;
+        let mut base_url = None;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--refresh" => refresh = true,
+                "--base-url" => {
+                    base_url = Some(args.next().ok_or(UsageError::MissingArgument("base-url"))?);
+                }
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        Ok(FetchArgs {
+            days,
+            refresh,
+            base_url,
+        })
+    }
+}
+
+struct BenchArgs {
+    selection: Selection,
+    runs: usize,
+    format: Format,
+}
+
+impl BenchArgs {
+    fn parse(args: &mut impl Iterator<Item = String>) -> Result<Self, UsageError> {
+        let day = args.next().ok_or(UsageError::MissingArgument("day"))?;
+        let selection = Selection::parse(day)?;
+
+        let mut runs = 10;
+        let mut format = Format::Table;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--runs" => {
+                    let n = args.next().ok_or(UsageError::MissingArgument("runs"))?;
+                    runs = match n.parse() {
+                        Ok(n) if n > 0 => n,
+                        _ => return Err(UsageError::BadRuns(n)),
+                    };
+                }
+                "--format" => {
+                    format = args
+                        .next()
+                        .ok_or(UsageError::MissingArgument("format"))?
+                        .parse()?;
+                }
+                _ => return Err(UsageError::UnknownOption(arg)),
+            }
+        }
+
+        Ok(BenchArgs {
+            selection,
+            runs,
+            format,
+        })
+    }
+}
+
+/// Flags override the input's params file, which overrides the puzzle's defaults
+fn run_day(
+    day: &Day,
+    input_path: &str,
+    part: Option<u8>,
+    overrides: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    let input = read_input(input_path)?;
+    let mut all_overrides = params::load_sidecar(input_path)?;
+    all_overrides.extend_from_slice(overrides);
+    let params = day.params(&all_overrides)?;
+
+    let parsed = day.parse(&input)?;
+    if part != Some(2) {
+        println!("{}", day.part1(parsed.as_ref(), params.as_ref())?);
+    }
+    if part != Some(1) {
+        println!("{}", day.part2(parsed.as_ref(), params.as_ref())?);
+    }
+    Ok(())
+}
+
+fn run(args: RunArgs) -> Result<(), Box<dyn Error>> {
+    match args.selection {
+        Selection::Single(day) => {
+            let input_path = args.input.unwrap_or_else(|| day.input_path());
+            run_day(&day, &input_path, args.part, &args.params)
+        }
+        Selection::All => {
+            // Keep going past a broken day so one failure doesn't hide the rest
+            let mut failures = 0;
+            for day in days::all() {
+                println!("Day {:02}", day.number());
+                if let Err(e) = run_day(&day, &day.input_path(), args.part, &[]) {
+                    failures += 1;
+                    println!("Error: {}", e);
+                }
+            }
+            if failures > 0 {
+                Err(RunFailed(failures).into())
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
+
+fn bench_days(args: BenchArgs) -> Result<(), Box<dyn Error>> {
+    let days = match args.selection {
+        Selection::All => days::all()
+            .into_iter()
+            .filter(|d| Path::new(&d.input_path()).exists())
+            .collect(),
+        Selection::Single(day) => vec![day],
+    };
+
+    let mut timings = Vec::with_capacity(days.len());
+    for day in days {
+        let input = read_input(day.input_path())?;
+        let params = day.params(&params::load_sidecar(day.input_path())?)?;
+        timings.push(bench::bench(&day, &input, params.as_ref(), args.runs)?);
+    }
+
+    print!("{}", bench::render(&timings, args.format));
+    Ok(())
+}
+
+fn fetch(args: FetchArgs) -> Result<(), Box<dyn Error>> {
+    let mut fetcher = Fetcher::from_env(args.base_url)?;
+    for day in args.days {
+        let path = format!("inputs/{:02}", day);
+        let outcome = fetcher.fetch(day, &path, args.refresh)?;
+        let status = match outcome {
+            Outcome::Cached => "cached",
+            Outcome::Downloaded => "downloaded",
+            Outcome::Unchanged => "unchanged",
+        };
+        println!("{:02} {} {}", day, path, status);
+    }
+    Ok(())
+}
+
+fn new_day(day: String) -> Result<(), Box<dyn Error>> {
+    let number = match day.parse() {
+        Ok(n) if (1..=25).contains(&n) => n,
+        _ => return Err(UsageError::UnknownDay(day).into()),
+    };
+    for path in scaffold::scaffold(Path::new(""), number)? {
+        println!("created {}", path.display());
+    }
+    Ok(())
+}
+
+fn verify_days(selection: Selection) -> Result<(), Box<dyn Error>> {
+    let cases = match selection {
+        // Only check inputs we have the answers for
+        Selection::All => days::all()
+            .into_iter()
+            .flat_map(|d| verify::cases(&d).into_iter().map(move |c| (d.number(), c)))
+            .collect::<Vec<_>>(),
+        Selection::Single(day) => {
+            let mut cases = verify::cases(&day);
+            // Asking for a day explicitly should report its missing input or answers
+            if !cases.iter().any(|c| c.name == "input") {
+                cases.insert(
+                    0,
+                    Case {
+                        name: "input".to_string(),
+                        input_path: day.input_path().into(),
+                        answers_path: day.answers_path().into(),
+                    },
+                );
+            }
+            cases.into_iter().map(|c| (day.number(), c)).collect()
+        }
+    };
+
+    let mut failures = 0;
+    println!("Day  Input      Part  Status   Expected             Actual");
+    for (number, case) in cases {
+        let day = days::get(number).expect("Registered day");
+        match verify::verify(&day, &case.input_path, &case.answers_path) {
+            Ok(results) => {
+                for result in results {
+                    let status = result.status();
+                    if status == Status::Fail {
+                        failures += 1;
+                    }
+                    println!(
+                        "{:<4} {:<10} {:<5} {:<8} {:<20} {}",
+                        format!("{:02}", number),
+                        case.name,
+                        result.part,
+                        status,
+                        result.expected.as_deref().unwrap_or("-"),
+                        result.actual
+                    );
+                }
+            }
+            Err(e) => {
+                failures += 1;
+                println!(
+                    "{:<4} {:<10} {:<5} {:<8} {}",
+                    format!("{:02}", number),
+                    case.name,
+                    "-",
+                    "error",
+                    e
+                );
+            }
+        }
+    }
+
+    if failures > 0 {
+        Err(VerifyFailed(failures).into())
+    } else {
+        Ok(())
+    }
+}
+
+fn list() {
+    for day in days::all() {
+        println!("{:02} {}", day.number(), day.input_path());
+    }
+}
+
+fn dispatch(mut args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let command = args.next().ok_or(UsageError::MissingArgument("command"))?;
+    match command.as_str() {
+        "list" => {
+            list();
+            Ok(())
+        }
+        "run" => run(RunArgs::parse(&mut args)?),
+        "bench" => bench_days(BenchArgs::parse(&mut args)?),
+        "fetch" => fetch(FetchArgs::parse(&mut args)?),
+        "new" => {
+            let day = args.next().ok_or(UsageError::MissingArgument("day"))?;
+            if let Some(arg) = args.next() {
+                return Err(UsageError::UnknownOption(arg).into());
+            }
+            new_day(day)
+        }
+        "verify" => {
+            let selection = Selection::parse(args.next().unwrap_or_else(|| "all".to_string()))?;
+            if let Some(arg) = args.next() {
+                return Err(UsageError::UnknownOption(arg).into());
+            }
+            verify_days(selection)
+        }
+        _ => Err(UsageError::UnknownCommand(command).into()),
+    }
+}
+
+fn main() -> ExitCode {
+    match dispatch(env::args().skip(1)) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            if e.is::<UsageError>() {
+                eprintln!("{}", USAGE);
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -1,59 +1,321 @@
+use super::io::normalise_input;
+use super::ocr::{self, OcrError};
+use super::render;
+use crate::error::AocError;
 use bitvec::prelude::*;
-use lazy_static::lazy_static;
-use std::cmp;
+use bitvec::ptr::Mut;
+use std::collections::hash_map;
 use std::collections::HashMap;
-
-lazy_static! {
-    static ref LETTERS: HashMap<u32, char> = {
-        let mut h = HashMap::new();
-        h.insert(529680320, 'A');
-        h.insert(1067881856, 'B');
-        h.insert(512103552, 'C');
-        h.insert(1065752448, 'D');
-        h.insert(1067882560, 'E');
-        h.insert(1067616256, 'F');
-        h.insert(512120256, 'G');
-        h.insert(1059098560, 'H');
-        h.insert(8910912, 'I');
-        h.insert(33955712, 'J');
-        h.insert(1059153984, 'K');
-        h.insert(1057230912, 'L');
-        h.insert(0, 'M');
-        h.insert(1059082176, 'N');
-        h.insert(512104320, 'O');
-        h.insert(1066550784, 'P');
-        h.insert(0, 'Q');
-        h.insert(1066559040, 'R');
-        h.insert(429283456, 'S');
-        h.insert(0, 'T');
-        h.insert(1040457600, 'U');
-        h.insert(941101496, 'V');
-        h.insert(0, 'W');
-        h.insert(0, 'X');
-        h.insert(807432752, 'Y');
-        h.insert(597072960, 'Z');
-        h.insert(0, ' ');
-        h
-    };
-    static ref ADJACENT: Vec<(isize, isize)> = vec![(0, -1), (0, 1), (1, 0), (-1, 0)];
-}
+use std::fmt::Display;
+use std::iter::Map;
+use std::ops::{DerefMut, Range};
 
 pub trait Grid {
     type Value;
     type Coordinate;
+    type Points<'a>: Iterator<Item = (Self::Coordinate, &'a Self::Value)>
+    where
+        Self: 'a;
+    /// A plain `&mut` for most grids, but bit grids hand out a proxy that writes the bit
+    /// back when dropped
+    type ValueMut<'a>: DerefMut<Target = Self::Value>
+    where
+        Self: 'a;
 
     fn height(&self) -> usize;
     fn width(&self) -> usize;
     fn at(&self, coord: &Self::Coordinate) -> Option<&Self::Value>;
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<Self::ValueMut<'_>>;
     fn set(&mut self, coord: Self::Coordinate, value: Self::Value);
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool;
+
+    /// The coordinate `column` cells right and `row` cells down from the top left corner
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate;
+
+    /// The inverse of `coordinate`, as (column, row). Negative or past the far edge for
+    /// coordinates outside the grid.
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize);
+
+    /// Every cell holding a value. Dense grids go row by row, sparse grids in no particular
+    /// order.
+    fn points(&self) -> Self::Points<'_>;
+
+    /// Each row from the top, as an iterator over its cells from the left
+    fn rows(&self) -> Lines<'_, Self>
+    where
+        Self: Sized,
+    {
+        Lines::new(self, Axis::Row)
+    }
+
+    /// Each column from the left, as an iterator over its cells from the top
+    fn columns(&self) -> Lines<'_, Self>
+    where
+        Self: Sized,
+    {
+        Lines::new(self, Axis::Column)
+    }
+
+    /// The cells at each offset in `stencil` from `coord` that are inside the grid and hold
+    /// a value
+    fn neighbours<'a>(
+        &'a self,
+        coord: &Self::Coordinate,
+        stencil: &'a [(isize, isize)],
+    ) -> Neighbours<'a, Self>
+    where
+        Self: Sized,
+    {
+        Neighbours::new(self, coord, stencil, false)
+    }
+
+    /// Like `neighbours`, but offsets that leave the grid wrap around to the opposite edge
+    fn wrapping_neighbours<'a>(
+        &'a self,
+        coord: &Self::Coordinate,
+        stencil: &'a [(isize, isize)],
+    ) -> Neighbours<'a, Self>
+    where
+        Self: Sized,
+    {
+        Neighbours::new(self, coord, stencil, true)
+    }
+
+    /// The four orthogonally adjacent cells
+    fn von_neumann(&self, coord: &Self::Coordinate) -> Neighbours<'_, Self>
+    where
+        Self: Sized,
+    {
+        self.neighbours(coord, &VON_NEUMANN)
+    }
+
+    /// The eight cells touching `coord`, including diagonally
+    fn moore(&self, coord: &Self::Coordinate) -> Neighbours<'_, Self>
+    where
+        Self: Sized,
+    {
+        self.neighbours(coord, &MOORE)
+    }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)>;
     fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = Self::Value>>) -> Self;
 }
 
-trait FullGrid: Grid {
-    fn row_for_point(p: &Self::Coordinate) -> usize;
-    fn column_for_point(p: &Self::Coordinate) -> usize;
+/// A grid with a value in every cell inside its bounds, unlike a `SparseGrid`
+pub trait DenseGrid: Grid {
+    /// A read only view of the whole grid, to rotate, flip or crop without copying
+    fn view(&self) -> View<'_, Self>
+    where
+        Self: Sized,
+    {
+        View::new(self)
+    }
+}
+
+impl DenseGrid for ResizingBitGrid {}
+impl DenseGrid for BitGrid {}
+impl<T: Default + Clone> DenseGrid for SingleVecGrid<T> {}
+impl<T> DenseGrid for VecGrid<T> {}
+impl<T: Default> DenseGrid for DynamicGrid<T> {}
+
+/// Offsets to the orthogonal neighbours, clockwise from up
+pub const VON_NEUMANN: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to every touching cell, clockwise from up
+pub const MOORE: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// The cells around a coordinate picked out by a stencil of offsets, see `Grid::neighbours`
+pub struct Neighbours<'a, G> {
+    grid: &'a G,
+    centre: (isize, isize),
+    stencil: std::slice::Iter<'a, (isize, isize)>,
+    wrap: bool,
+}
+
+impl<'a, G: Grid> Neighbours<'a, G> {
+    pub fn new(
+        grid: &'a G,
+        coord: &G::Coordinate,
+        stencil: &'a [(isize, isize)],
+        wrap: bool,
+    ) -> Self {
+        Neighbours {
+            grid,
+            centre: grid.position(coord),
+            stencil: stencil.iter(),
+            wrap,
+        }
+    }
+}
+
+impl<'a, G: Grid> Iterator for Neighbours<'a, G> {
+    type Item = (G::Coordinate, &'a G::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (width, height) = (self.grid.width() as isize, self.grid.height() as isize);
+        for (dx, dy) in self.stencil.by_ref() {
+            let (mut column, mut row) = (self.centre.0 + dx, self.centre.1 + dy);
+            if self.wrap && width > 0 && height > 0 {
+                column = column.rem_euclid(width);
+                row = row.rem_euclid(height);
+            }
+            if !(0..width).contains(&column) || !(0..height).contains(&row) {
+                continue;
+            }
+            let coord = self.grid.coordinate(column as usize, row as usize);
+            if let Some(value) = self.grid.at(&coord) {
+                return Some((coord, value));
+            }
+        }
+        None
+    }
+}
+
+/// Flattens rows into row-major order, returning the values with the width and height.
+/// Panics if the rows differ in length.
+fn flatten_rows<T>(
+    source: impl IntoIterator<Item = impl IntoIterator<Item = T>>,
+) -> (Vec<T>, usize, usize) {
+    let mut values = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+    for row in source {
+        let start = values.len();
+        values.extend(row);
+        let row_width = values.len() - start;
+        match width {
+            Some(w) => assert_eq!(w, row_width, "Row {} has a different width", height),
+            None => width = Some(row_width),
+        }
+        height += 1;
+    }
+    (values, width.unwrap_or(0), height)
+}
+
+/// Row by row iterator over a grid with a value in every cell
+pub struct DensePoints<'a, G> {
+    grid: &'a G,
+    next: usize,
+}
+
+impl<'a, G> DensePoints<'a, G> {
+    fn new(grid: &'a G) -> Self {
+        DensePoints { grid, next: 0 }
+    }
+}
+
+impl<'a, G: Grid> Iterator for DensePoints<'a, G> {
+    type Item = (G::Coordinate, &'a G::Value);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let width = self.grid.width();
+        if self.next >= width * self.grid.height() {
+            return None;
+        }
+        let coord = self.grid.coordinate(self.next % width, self.next / width);
+        self.next += 1;
+        let value = self.grid.at(&coord)?;
+        Some((coord, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.grid.width() * self.grid.height()).saturating_sub(self.next);
+        (remaining, Some(remaining))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Axis {
+    Row,
+    Column,
+}
+
+/// The rows or columns of a grid, see `Grid::rows` and `Grid::columns`
+pub struct Lines<'a, G> {
+    grid: &'a G,
+    axis: Axis,
+    remaining: Range<usize>,
+}
+
+impl<'a, G: Grid> Lines<'a, G> {
+    pub fn new(grid: &'a G, axis: Axis) -> Self {
+        let count = match axis {
+            Axis::Row => grid.height(),
+            Axis::Column => grid.width(),
+        };
+        Lines {
+            grid,
+            axis,
+            remaining: 0..count,
+        }
+    }
+
+    fn line(&self, index: usize) -> Line<'a, G> {
+        let length = match self.axis {
+            Axis::Row => self.grid.width(),
+            Axis::Column => self.grid.height(),
+        };
+        Line {
+            grid: self.grid,
+            axis: self.axis,
+            index,
+            remaining: 0..length,
+        }
+    }
+}
+
+impl<'a, G: Grid> Iterator for Lines<'a, G> {
+    type Item = Line<'a, G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining.next().map(|i| self.line(i))
+    }
+}
+
+impl<'a, G: Grid> DoubleEndedIterator for Lines<'a, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.remaining.next_back().map(|i| self.line(i))
+    }
+}
+
+/// The cells of one row or column. Cells a sparse grid has no value for are `None`.
+pub struct Line<'a, G> {
+    grid: &'a G,
+    axis: Axis,
+    index: usize,
+    remaining: Range<usize>,
+}
+
+impl<'a, G: Grid> Line<'a, G> {
+    fn cell(&self, i: usize) -> Option<&'a G::Value> {
+        let coord = match self.axis {
+            Axis::Row => self.grid.coordinate(i, self.index),
+            Axis::Column => self.grid.coordinate(self.index, i),
+        };
+        self.grid.at(&coord)
+    }
+}
+
+impl<'a, G: Grid> Iterator for Line<'a, G> {
+    type Item = Option<&'a G::Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining.next().map(|i| self.cell(i))
+    }
+}
+
+impl<'a, G: Grid> DoubleEndedIterator for Line<'a, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.remaining.next_back().map(|i| self.cell(i))
+    }
 }
 
 pub struct ResizingBitGrid {
@@ -106,6 +368,8 @@ impl ResizingBitGrid {
 impl Grid for ResizingBitGrid {
     type Value = bool;
     type Coordinate = (usize, usize);
+    type Points<'a> = DensePoints<'a, Self>;
+    type ValueMut<'a> = BitRef<'a, Mut>;
 
     fn height(&self) -> usize {
         self.height
@@ -120,6 +384,12 @@ impl Grid for ResizingBitGrid {
         self.index(*x, *y).map(|i| &self.values[i])
     }
 
+    /// Unlike `set`, doesn't grow the grid
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<BitRef<'_, Mut>> {
+        let (x, y) = coord;
+        self.index(*x, *y).and_then(|i| self.values.get_mut(i))
+    }
+
     fn set(&mut self, coord: Self::Coordinate, value: Self::Value) {
         let (x, y) = coord;
 
@@ -144,12 +414,29 @@ impl Grid for ResizingBitGrid {
         *self.values.get_mut(i).unwrap() = value
     }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)> {
-        todo!()
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        self.index(coord.0, coord.1).is_some()
     }
 
-    fn from_rows(_: impl IntoIterator<Item = impl IntoIterator<Item = Self::Value>>) -> Self {
-        todo!()
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        (column, row)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        (coord.0 as isize, coord.1 as isize)
+    }
+
+    fn points(&self) -> DensePoints<'_, Self> {
+        DensePoints::new(self)
+    }
+
+    fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = bool>>) -> Self {
+        let (values, width, height) = flatten_rows(source);
+        Self {
+            values: values.into_iter().collect(),
+            width,
+            height,
+        }
     }
 }
 
@@ -182,44 +469,23 @@ impl BitGrid {
     }
 
     pub fn print(&self, true_val: char, false_val: char) {
-        for y in 0..self.height() {
-            let row = (0..self.width())
-                .map(|x| {
-                    if *self.at(&(x, y)).unwrap() {
-                        true_val
-                    } else {
-                        false_val
-                    }
-                })
-                .collect::<String>();
-            println!("{}", row);
-        }
-    }
-
-    pub fn decode_string(&self) -> String {
-        let font_width = 5;
-        let font_height = 6;
-        let mut x = 0;
-        let mut s = String::new();
-        while (x + font_width) <= self.width() + 1 {
-            let mut letter_bits = BitVec::<u32, Msb0>::with_capacity(32);
-            for xd in 0..font_width {
-                let x = xd + x;
-                for y in 0..font_height {
-                    letter_bits.push(*self.at(&(x, y)).unwrap_or(&false));
-                }
-            }
-            let c = *LETTERS.get(&letter_bits.load::<u32>()).unwrap_or(&'?');
-            s.push(c);
-            x += font_width;
-        }
-        s
+        print!(
+            "{}",
+            render::to_string(self, |v| if *v { true_val } else { false_val })
+        );
+    }
+
+    /// Reads the block letters drawn in the set cells, as some puzzles give their answers
+    pub fn decode_string(&self) -> Result<String, OcrError> {
+        ocr::decode(self)
     }
 }
 
 impl Grid for BitGrid {
     type Value = bool;
     type Coordinate = (usize, usize);
+    type Points<'a> = DensePoints<'a, Self>;
+    type ValueMut<'a> = BitRef<'a, Mut>;
 
     fn height(&self) -> usize {
         self.height
@@ -234,6 +500,11 @@ impl Grid for BitGrid {
         self.index(*x, *y).map(|i| &self.values[i])
     }
 
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<BitRef<'_, Mut>> {
+        let (x, y) = coord;
+        self.index(*x, *y).and_then(|i| self.values.get_mut(i))
+    }
+
     fn set(&mut self, coord: Self::Coordinate, value: bool) {
         let (x, y) = coord;
 
@@ -243,12 +514,29 @@ impl Grid for BitGrid {
         }
     }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)> {
-        panic!("Not implemented");
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        self.index(coord.0, coord.1).is_some()
+    }
+
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        (column, row)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        (coord.0 as isize, coord.1 as isize)
     }
 
-    fn from_rows(_: impl IntoIterator<Item = impl IntoIterator<Item = bool>>) -> Self {
-        panic!("Not implemented");
+    fn points(&self) -> DensePoints<'_, Self> {
+        DensePoints::new(self)
+    }
+
+    fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = bool>>) -> Self {
+        let (values, width, height) = flatten_rows(source);
+        Self {
+            values: values.into_iter().collect(),
+            width,
+            height,
+        }
     }
 }
 
@@ -314,20 +602,7 @@ where
         new_grid
     }
 
-    pub fn adjacent(&self, coord: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &T)> {
-        ADJACENT
-            .iter()
-            .map(move |off| (coord.0 as isize + off.0, coord.1 as isize + off.1))
-            .filter(|(x, y)| {
-                *x >= 0 && *x < self.width() as isize && *y >= 0 && *y < self.height() as isize
-            })
-            .map(|(x, y)| {
-                let coord = (x as usize, y as usize);
-                (coord, self.at(&coord).unwrap())
-            })
-    }
-
-    pub fn raycast(&self, from: (usize, usize), step: (isize, isize)) -> RaycastIterator<Self> {
+    pub fn raycast(&self, from: (usize, usize), step: (isize, isize)) -> RaycastIterator<'_, Self> {
         RaycastIterator {
             grid: self,
             step,
@@ -370,6 +645,14 @@ where
 {
     type Value = T;
     type Coordinate = (usize, usize);
+    type Points<'a>
+        = DensePoints<'a, Self>
+    where
+        T: 'a;
+    type ValueMut<'a>
+        = &'a mut T
+    where
+        T: 'a;
 
     fn height(&self) -> usize {
         self.height
@@ -384,6 +667,11 @@ where
         self.index(*x, *y).map(|i| &self.values[i])
     }
 
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<&mut T> {
+        let (x, y) = coord;
+        self.index(*x, *y).map(|i| &mut self.values[i])
+    }
+
     fn set(&mut self, coord: Self::Coordinate, value: T) {
         let (x, y) = coord;
 
@@ -393,20 +681,29 @@ where
         }
     }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)> {
-        self.values
-            .iter()
-            .enumerate()
-            .map(|(i, value)| {
-                let x = i % self.width;
-                let y = i / self.width;
-                ((x, y), value)
-            })
-            .collect()
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        self.index(coord.0, coord.1).is_some()
+    }
+
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        (column, row)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        (coord.0 as isize, coord.1 as isize)
     }
 
-    fn from_rows(_: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
-        panic!("Not implemented");
+    fn points(&self) -> DensePoints<'_, Self> {
+        DensePoints::new(self)
+    }
+
+    fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
+        let (values, width, height) = flatten_rows(source);
+        Self {
+            values,
+            width,
+            height,
+        }
     }
 }
 
@@ -452,7 +749,7 @@ impl<T> VecGrid<T> {
             // There are all empty edges when we don't have any content
             vec![Vec::new(), Vec::new(), Vec::new(), Vec::new()]
         } else {
-            let top = self.rows.get(0).unwrap().iter().collect();
+            let top = self.rows.first().unwrap().iter().collect();
             let bottom = self.rows.get(self.height() - 1).unwrap().iter().collect();
             let mut right = Vec::with_capacity(self.height());
             let mut left = Vec::with_capacity(self.height());
@@ -469,6 +766,14 @@ impl<T> VecGrid<T> {
 impl<T> Grid for VecGrid<T> {
     type Value = T;
     type Coordinate = (usize, usize);
+    type Points<'a>
+        = DensePoints<'a, Self>
+    where
+        T: 'a;
+    type ValueMut<'a>
+        = &'a mut T
+    where
+        T: 'a;
 
     fn height(&self) -> usize {
         self.rows.len()
@@ -483,6 +788,11 @@ impl<T> Grid for VecGrid<T> {
         self.rows.get(*y).and_then(|row| row.get(*x))
     }
 
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<&mut T> {
+        let (x, y) = coord;
+        self.rows.get_mut(*y).and_then(|row| row.get_mut(*x))
+    }
+
     fn set(&mut self, coord: Self::Coordinate, value: T) {
         let (x, y) = coord;
         if x >= self.width.unwrap_or(0) {
@@ -502,12 +812,20 @@ impl<T> Grid for VecGrid<T> {
         self.rows[y][x] = value;
     }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)> {
-        self.rows
-            .iter()
-            .enumerate()
-            .flat_map(|(y, row)| row.iter().enumerate().map(move |(x, v)| ((x, y), v)))
-            .collect()
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        coord.0 < self.width() && coord.1 < self.height()
+    }
+
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        (column, row)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        (coord.0 as isize, coord.1 as isize)
+    }
+
+    fn points(&self) -> DensePoints<'_, Self> {
+        DensePoints::new(self)
     }
 
     fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
@@ -526,59 +844,274 @@ impl<T> Default for VecGrid<T> {
     }
 }
 
+/// A dense grid keyed by signed coordinates that grows in any direction to fit the cells set.
+/// Cells inside the bounding box that were never set hold `T::default()`.
+#[derive(Debug, Clone)]
+pub struct DynamicGrid<T> {
+    values: Vec<T>,
+    /// Coordinate of the first value and the allocated size, which leaves room to grow
+    origin: (isize, isize),
+    capacity: (usize, usize),
+    /// Top left and bottom right corners of the cells set so far
+    bounds: Option<((isize, isize), (isize, isize))>,
+}
+
+impl<T> DynamicGrid<T> {
+    pub fn new() -> Self {
+        DynamicGrid {
+            values: Vec::new(),
+            origin: (0, 0),
+            capacity: (0, 0),
+            bounds: None,
+        }
+    }
+
+    /// Top left and bottom right corners of the cells set so far, both inclusive
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        self.bounds
+    }
+}
+
+impl<T: Default> DynamicGrid<T> {
+    /// A grid covering the box from `top_left` to `bottom_right` inclusive, every cell default
+    pub fn with_bounds(top_left: (isize, isize), bottom_right: (isize, isize)) -> Self {
+        let mut grid = DynamicGrid::new();
+        grid.reserve(top_left);
+        grid.reserve(bottom_right);
+        grid.bounds = Some((top_left, bottom_right));
+        grid
+    }
+
+    fn index(&self, coord: &(isize, isize)) -> Option<usize> {
+        if !self.in_bounds(coord) {
+            return None;
+        }
+        let x = (coord.0 - self.origin.0) as usize;
+        let y = (coord.1 - self.origin.1) as usize;
+        Some(y * self.capacity.0 + x)
+    }
+
+    /// Makes sure the allocation covers `coord`, growing by at least the current size in
+    /// each direction that's too small so repeated growth stays cheap
+    fn reserve(&mut self, coord: (isize, isize)) {
+        let (x, y) = coord;
+        let (left, top) = self.origin;
+        let (width, height) = (self.capacity.0 as isize, self.capacity.1 as isize);
+        if self.capacity != (0, 0)
+            && (left..left + width).contains(&x)
+            && (top..top + height).contains(&y)
+        {
+            return;
+        }
+
+        let (new_left, new_right, new_top, new_bottom) = if self.capacity == (0, 0) {
+            (x, x + 1, y, y + 1)
+        } else {
+            (
+                if x < left { x - width } else { left },
+                if x >= left + width {
+                    x + 1 + width
+                } else {
+                    left + width
+                },
+                if y < top { y - height } else { top },
+                if y >= top + height {
+                    y + 1 + height
+                } else {
+                    top + height
+                },
+            )
+        };
+        let new_width = (new_right - new_left) as usize;
+        let new_height = (new_bottom - new_top) as usize;
+
+        let mut values = Vec::with_capacity(new_width * new_height);
+        values.resize_with(new_width * new_height, T::default);
+        for (i, value) in std::mem::take(&mut self.values).into_iter().enumerate() {
+            let x = left + (i % self.capacity.0) as isize - new_left;
+            let y = top + (i / self.capacity.0) as isize - new_top;
+            values[y as usize * new_width + x as usize] = value;
+        }
+        self.values = values;
+        self.origin = (new_left, new_top);
+        self.capacity = (new_width, new_height);
+    }
+}
+
+impl<T: Default> Grid for DynamicGrid<T> {
+    type Value = T;
+    type Coordinate = (isize, isize);
+    type Points<'a>
+        = DensePoints<'a, Self>
+    where
+        T: 'a;
+    type ValueMut<'a>
+        = &'a mut T
+    where
+        T: 'a;
+
+    fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.1 - min.1 + 1) as usize)
+    }
+
+    fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.0 - min.0 + 1) as usize)
+    }
+
+    fn at(&self, coord: &Self::Coordinate) -> Option<&T> {
+        self.index(coord).map(|i| &self.values[i])
+    }
+
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<&mut T> {
+        self.index(coord).map(|i| &mut self.values[i])
+    }
+
+    fn set(&mut self, coord: Self::Coordinate, value: T) {
+        self.reserve(coord);
+        let (x, y) = coord;
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+            None => (coord, coord),
+        });
+        let i = self.index(&coord).expect("Should now have index in range!");
+        self.values[i] = value;
+    }
+
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.0..=max.0).contains(&coord.0) && (min.1..=max.1).contains(&coord.1)
+        })
+    }
+
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        let (left, top) = self.bounds.map_or((0, 0), |(min, _)| min);
+        (left + column as isize, top + row as isize)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        let (left, top) = self.bounds.map_or((0, 0), |(min, _)| min);
+        (coord.0 - left, coord.1 - top)
+    }
+
+    fn points(&self) -> DensePoints<'_, Self> {
+        DensePoints::new(self)
+    }
+
+    fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
+        let (values, width, height) = flatten_rows(source);
+        let bounds = if width == 0 || height == 0 {
+            None
+        } else {
+            Some(((0, 0), (width as isize - 1, height as isize - 1)))
+        };
+        DynamicGrid {
+            values,
+            origin: (0, 0),
+            capacity: (width, height),
+            bounds,
+        }
+    }
+}
+
+impl<T> Default for DynamicGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Grids are equal when they cover the same cells with the same values, however much room
+/// each has left to grow
+impl<T: Default + PartialEq> PartialEq for DynamicGrid<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bounds == other.bounds && self.points().eq(other.points())
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct SparseGrid<T> {
     cells: HashMap<(isize, isize), T>,
+    /// Top left and bottom right corners of the cells set so far
+    bounds: Option<((isize, isize), (isize, isize))>,
 }
 
+pub type SparsePoints<'a, T> = Map<
+    hash_map::Iter<'a, (isize, isize), T>,
+    fn((&'a (isize, isize), &'a T)) -> ((isize, isize), &'a T),
+>;
+
 impl<T> SparseGrid<T> {
     pub fn new() -> Self {
         SparseGrid {
             cells: HashMap::new(),
+            bounds: None,
         }
     }
 
-    fn key_range<F>(&self, key_fn: F) -> usize
-    where
-        F: Fn(isize, isize) -> isize,
-    {
-        if self.cells.is_empty() {
-            0
-        } else {
-            let mut min = isize::MAX;
-            let mut max = isize::MIN;
-            for k in self.cells.keys() {
-                let v = key_fn(k.0, k.1);
-                min = cmp::min(min, v);
-                max = cmp::max(max, v);
-            }
-            (max - min + 1) as usize
-        }
+    fn top_left(&self) -> (isize, isize) {
+        self.bounds.map_or((0, 0), |(min, _)| min)
     }
 }
 
 impl<T> Grid for SparseGrid<T> {
     type Value = T;
     type Coordinate = (isize, isize);
+    type Points<'a>
+        = SparsePoints<'a, T>
+    where
+        T: 'a;
+    type ValueMut<'a>
+        = &'a mut T
+    where
+        T: 'a;
 
     fn height(&self) -> usize {
-        self.key_range(|_, y| y)
+        self.bounds
+            .map_or(0, |(min, max)| (max.1 - min.1 + 1) as usize)
     }
 
     fn width(&self) -> usize {
-        self.key_range(|x, _| x)
+        self.bounds
+            .map_or(0, |(min, max)| (max.0 - min.0 + 1) as usize)
     }
 
     fn at(&self, coord: &Self::Coordinate) -> Option<&T> {
         self.cells.get(coord)
     }
 
+    fn at_mut(&mut self, coord: &Self::Coordinate) -> Option<&mut T> {
+        self.cells.get_mut(coord)
+    }
+
     fn set(&mut self, coord: Self::Coordinate, val: T) {
+        let (x, y) = coord;
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y))),
+            None => (coord, coord),
+        });
         self.cells.insert(coord, val);
     }
 
-    fn points(&self) -> Vec<(Self::Coordinate, &Self::Value)> {
-        self.cells.iter().map(|(coord, v)| (*coord, v)).collect()
+    /// Whether the coordinate is within the bounding box of the cells set so far
+    fn in_bounds(&self, coord: &Self::Coordinate) -> bool {
+        self.bounds.is_some_and(|(min, max)| {
+            (min.0..=max.0).contains(&coord.0) && (min.1..=max.1).contains(&coord.1)
+        })
+    }
+
+    fn coordinate(&self, column: usize, row: usize) -> Self::Coordinate {
+        let (left, top) = self.top_left();
+        (left + column as isize, top + row as isize)
+    }
+
+    fn position(&self, coord: &Self::Coordinate) -> (isize, isize) {
+        let (left, top) = self.top_left();
+        (coord.0 - left, coord.1 - top)
+    }
+
+    fn points(&self) -> SparsePoints<'_, T> {
+        self.cells.iter().map(|(coord, v)| (*coord, v))
     }
 
     fn from_rows(source: impl IntoIterator<Item = impl IntoIterator<Item = T>>) -> Self {
@@ -605,13 +1138,710 @@ where
     fn clone(&self) -> Self {
         SparseGrid {
             cells: self.cells.clone(),
+            bounds: self.bounds,
         }
     }
 }
 
+/// A rotated, flipped or cropped window onto a dense grid, made with `DenseGrid::view`. Nothing
+/// is copied: each cell of the view is looked up in the grid it borrows, and transforms
+/// chain, so `grid.view().crop(1, 1, 3, 3).rotate_clockwise()` is a turned window. Use
+/// `to_grid` for an owned copy, such as to hand to code that takes a `Grid`.
+///
+/// Views are read only, with `(column, row)` coordinates from their own top left corner.
+pub struct View<'a, G> {
+    grid: &'a G,
+    /// Position in the grid of the view's top left cell
+    origin: (isize, isize),
+    /// Step in the grid for one column right in the view
+    across: (isize, isize),
+    /// Step in the grid for one row down in the view
+    down: (isize, isize),
+    width: usize,
+    height: usize,
+}
+
+impl<'a, G> Clone for View<'a, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, G> Copy for View<'a, G> {}
+
+impl<'a, G: DenseGrid> View<'a, G> {
+    pub fn new(grid: &'a G) -> Self {
+        View {
+            grid,
+            origin: (0, 0),
+            across: (1, 0),
+            down: (0, 1),
+            width: grid.width(),
+            height: grid.height(),
+        }
+    }
+
+    /// Where `steps` cells along `step` from the origin lands in the grid
+    fn offset(&self, steps: usize, step: (isize, isize)) -> (isize, isize) {
+        let steps = steps as isize;
+        (
+            self.origin.0 + steps * step.0,
+            self.origin.1 + steps * step.1,
+        )
+    }
+
+    /// The grid's (column, row) position of a cell in the view
+    fn grid_position(&self, column: usize, row: usize) -> (usize, usize) {
+        let (column, row) = (column as isize, row as isize);
+        let x = self.origin.0 + column * self.across.0 + row * self.down.0;
+        let y = self.origin.1 + column * self.across.1 + row * self.down.1;
+        (x as usize, y as usize)
+    }
+
+    /// The coordinate in the borrowed grid of the view's cell at `coord`
+    pub fn source(&self, coord: &(usize, usize)) -> G::Coordinate {
+        let (x, y) = self.grid_position(coord.0, coord.1);
+        self.grid.coordinate(x, y)
+    }
+
+    /// Swaps rows and columns, mirroring along the top left to bottom right diagonal
+    pub fn transpose(self) -> Self {
+        View {
+            across: self.down,
+            down: self.across,
+            width: self.height,
+            height: self.width,
+            ..self
+        }
+    }
+
+    /// Mirrors left to right
+    pub fn flip_horizontal(self) -> Self {
+        View {
+            origin: self.offset(self.width.saturating_sub(1), self.across),
+            across: (-self.across.0, -self.across.1),
+            ..self
+        }
+    }
+
+    /// Mirrors top to bottom
+    pub fn flip_vertical(self) -> Self {
+        View {
+            origin: self.offset(self.height.saturating_sub(1), self.down),
+            down: (-self.down.0, -self.down.1),
+            ..self
+        }
+    }
+
+    /// A quarter turn clockwise, so the left column becomes the top row
+    pub fn rotate_clockwise(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// A quarter turn anticlockwise, so the top row becomes the left column
+    pub fn rotate_anticlockwise(self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    pub fn rotate_half(self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// The `width` by `height` window with its top left corner at `column`, `row` of this
+    /// view, trimmed to fit inside it
+    pub fn crop(self, column: usize, row: usize, width: usize, height: usize) -> Self {
+        let column = column.min(self.width);
+        let row = row.min(self.height);
+        let start = self.offset(column, self.across);
+        View {
+            origin: (
+                start.0 + row as isize * self.down.0,
+                start.1 + row as isize * self.down.1,
+            ),
+            width: width.min(self.width - column),
+            height: height.min(self.height - row),
+            ..self
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn in_bounds(&self, coord: &(usize, usize)) -> bool {
+        coord.0 < self.width && coord.1 < self.height
+    }
+
+    pub fn at(&self, coord: &(usize, usize)) -> Option<&'a G::Value> {
+        if !self.in_bounds(coord) {
+            return None;
+        }
+        self.grid.at(&self.source(coord))
+    }
+
+    /// Each row from the top, as an iterator over its cells from the left
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = Option<&'a G::Value>>> + 'a {
+        let view = *self;
+        (0..self.height).map(move |y| (0..view.width).map(move |x| view.at(&(x, y))))
+    }
+
+    /// Copies the view into a grid of its own
+    pub fn to_grid<H>(&self) -> H
+    where
+        H: Grid<Value = G::Value>,
+        G::Value: Clone,
+    {
+        H::from_rows(
+            self.rows()
+                .map(|row| row.map(|v| v.expect("dense grids hold every cell").clone())),
+        )
+    }
+}
+
+/// A grid read from text by `parse_text`, along with where its marker characters were
+pub struct TextGrid<G: Grid> {
+    pub grid: G,
+    markers: Vec<(char, G::Coordinate)>,
+}
+
+/// Reads a block of text into any kind of grid, one cell per character, with `cell` mapping
+/// each character to its value. The coordinates of every character in `markers` are kept,
+/// for things like a start and end square. Errors from `cell`, and rows that differ in
+/// length from the first, are reported at their line and column. CRLF line endings and
+/// trailing blank lines are dropped first, so neither leaves a ragged or extra row.
+///
+/// ```ignore
+/// let map: TextGrid<SingleVecGrid<u8>> = parse_text(input, &['S', 'E'], |c| match c {
+///     'S' => Ok(0),
+///     'E' => Ok(25),
+///     'a'..='z' => Ok(c as u8 - b'a'),
+///     _ => Err(format!("unexpected {:?}", c)),
+/// })?;
+/// ```
+pub fn parse_text<G, E>(
+    text: &str,
+    markers: &[char],
+    mut cell: impl FnMut(char) -> Result<G::Value, E>,
+) -> Result<TextGrid<G>, AocError>
+where
+    G: Grid,
+    E: Display,
+{
+    let mut rows = Vec::new();
+    let mut positions = Vec::new();
+    let mut width = None;
+    for (y, line) in normalise_input(text).lines().enumerate() {
+        let mut row = Vec::new();
+        for (x, c) in line.chars().enumerate() {
+            if markers.contains(&c) {
+                positions.push((c, x, y));
+            }
+            row.push(cell(c).map_err(|e| AocError::parse(y + 1, x + 1, e))?);
+        }
+        match width {
+            Some(w) if w != row.len() => {
+                return Err(AocError::parse(
+                    y + 1,
+                    row.len().min(w) + 1,
+                    format!("row is {} wide, expected {}", row.len(), w),
+                ))
+            }
+            _ => width = Some(row.len()),
+        }
+        rows.push(row);
+    }
+
+    let grid = G::from_rows(rows);
+    let markers = positions
+        .into_iter()
+        .map(|(c, x, y)| (c, grid.coordinate(x, y)))
+        .collect();
+    Ok(TextGrid { grid, markers })
+}
+
+impl<G: Grid> TextGrid<G> {
+    /// Where `marker` first appeared
+    pub fn marker(&self, marker: char) -> Option<&G::Coordinate> {
+        self.markers(marker).next()
+    }
+
+    /// Everywhere `marker` appeared, row by row
+    pub fn markers(&self, marker: char) -> impl Iterator<Item = &G::Coordinate> + '_ {
+        self.markers
+            .iter()
+            .filter(move |(c, _)| *c == marker)
+            .map(|(_, coord)| coord)
+    }
+
+    /// Writes the grid back out as `parse_text` read it, with `cell` drawing each value and
+    /// the markers put back in their places
+    pub fn to_text(&self, mut cell: impl FnMut(&G::Value) -> char) -> String {
+        let markers = self
+            .markers
+            .iter()
+            .map(|(c, coord)| (self.grid.position(coord), *c))
+            .collect::<HashMap<_, _>>();
+        let mut s = String::new();
+        for (y, row) in self.grid.rows().enumerate() {
+            for (x, value) in row.enumerate() {
+                s.push(match markers.get(&(x as isize, y as isize)) {
+                    Some(c) => *c,
+                    None => value.map_or(' ', &mut cell),
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+/// Where two grids disagree, found by `grid_diff`
+pub struct GridDiff<'a, V> {
+    left: Vec<Vec<Option<&'a V>>>,
+    right: Vec<Vec<Option<&'a V>>>,
+    width: usize,
+    differences: Vec<(usize, usize)>,
+}
+
+fn cells<G: Grid>(grid: &G) -> Vec<Vec<Option<&G::Value>>> {
+    grid.rows().map(|row| row.collect()).collect()
+}
+
+/// Compares two grids cell by cell, matching cells by their (column, row) position from the
+/// top left corner, so grids of different kinds or with different origins can be compared.
+/// A cell only one grid has, whether a sparse grid's hole or past the edge of the smaller
+/// grid, counts as a difference.
+pub fn grid_diff<'a, A, B>(left: &'a A, right: &'a B) -> GridDiff<'a, A::Value>
+where
+    A: Grid,
+    B: Grid<Value = A::Value>,
+    A::Value: PartialEq,
+{
+    let mut diff = GridDiff {
+        left: cells(left),
+        right: cells(right),
+        width: left.width().max(right.width()),
+        differences: Vec::new(),
+    };
+    let height = left.height().max(right.height());
+    for row in 0..height {
+        for column in 0..diff.width {
+            if diff.left_at(column, row) != diff.right_at(column, row) {
+                diff.differences.push((column, row));
+            }
+        }
+    }
+    diff
+}
+
+fn cell_at<'a, V>(cells: &[Vec<Option<&'a V>>], column: usize, row: usize) -> Option<&'a V> {
+    cells.get(row)?.get(column).copied().flatten()
+}
+
+impl<'a, V> GridDiff<'a, V> {
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+
+    /// The (column, row) positions of the cells that differ, row by row
+    pub fn differences(&self) -> &[(usize, usize)] {
+        &self.differences
+    }
+
+    pub fn left_at(&self, column: usize, row: usize) -> Option<&'a V> {
+        cell_at(&self.left, column, row)
+    }
+
+    pub fn right_at(&self, column: usize, row: usize) -> Option<&'a V> {
+        cell_at(&self.right, column, row)
+    }
+
+    fn height(&self) -> usize {
+        self.left.len().max(self.right.len())
+    }
+
+    /// Both grids next to each other, left then right, with rows that differ marked by `<`.
+    /// Cells a grid doesn't have are blank.
+    pub fn side_by_side(&self, mut cell: impl FnMut(&V) -> char) -> String {
+        let mut s = String::new();
+        for row in 0..self.height() {
+            for column in 0..self.width {
+                s.push(self.left_at(column, row).map_or(' ', &mut cell));
+            }
+            s.push_str(" | ");
+            for column in 0..self.width {
+                s.push(self.right_at(column, row).map_or(' ', &mut cell));
+            }
+            if self.differences.iter().any(|&(_, r)| r == row) {
+                s.push_str(" <");
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// The left grid with every cell that differs from the right one drawn as `!`
+    pub fn overlay(&self, mut cell: impl FnMut(&V) -> char) -> String {
+        let mut s = String::new();
+        for row in 0..self.height() {
+            for column in 0..self.width {
+                s.push(if self.differences.contains(&(column, row)) {
+                    '!'
+                } else {
+                    self.left_at(column, row).map_or(' ', &mut cell)
+                });
+            }
+            s.push('\n');
+        }
+        s
+    }
+
+    /// A summary of the differences followed by the side by side view, as `assert_grid_eq!`
+    /// prints
+    pub fn report(&self, cell: impl FnMut(&V) -> char) -> String {
+        let shown = self
+            .differences
+            .iter()
+            .take(10)
+            .map(|(column, row)| format!("({}, {})", column, row))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let more = if self.differences.len() > 10 {
+            ", ..."
+        } else {
+            ""
+        };
+        format!(
+            "{} cells differ at {}{}\n{}",
+            self.differences.len(),
+            shown,
+            more,
+            self.side_by_side(cell)
+        )
+    }
+}
+
+/// Panics with the side by side view of the two grids unless every cell matches, see
+/// `grid_diff`. Cells are drawn with `render::CellChar` unless a closure mapping a value to
+/// a `char` is given.
+///
+/// ```ignore
+/// assert_grid_eq!(trail, golden);
+/// assert_grid_eq!(heights, expected, |h: &u8| (b'0' + h) as char);
+/// ```
+#[macro_export]
+macro_rules! assert_grid_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_grid_eq!($left, $right, $crate::commons::render::CellChar::cell_char)
+    };
+    ($left:expr, $right:expr, $cell:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let diff = $crate::commons::grid::grid_diff(left, right);
+                if !diff.is_empty() {
+                    panic!("grids differ: {}", diff.report($cell));
+                }
+            }
+        }
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::commons::render::CellChar;
+    use std::fmt::Debug;
+
+    /// Checks the whole `Grid` trait against a grid built from `rows`. `other` must differ
+    /// from the values at (1, 2) and (3, 0), which get overwritten with it.
+    fn conformance<G>(rows: [[G::Value; 4]; 3], other: G::Value)
+    where
+        G: Grid,
+        G::Value: Clone + PartialEq + Debug,
+        G::Coordinate: Ord + Debug,
+    {
+        let mut grid = G::from_rows(rows.clone());
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+
+        for (y, row) in rows.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let coord = grid.coordinate(x, y);
+                assert!(grid.in_bounds(&coord), "{:?} in bounds", coord);
+                assert_eq!(grid.at(&coord), Some(value), "value at {:?}", coord);
+            }
+        }
+        for (x, y) in [(4, 0), (0, 3), (4, 3)] {
+            let coord = grid.coordinate(x, y);
+            assert!(!grid.in_bounds(&coord), "{:?} out of bounds", coord);
+            assert_eq!(grid.at(&coord), None);
+        }
+
+        let mut points = grid.points().collect::<Vec<_>>();
+        points.sort_by(|a, b| a.0.cmp(&b.0));
+        let mut expected = (0..3)
+            .flat_map(|y| (0..4).map(move |x| (x, y)))
+            .map(|(x, y)| (grid.coordinate(x, y), &rows[y][x]))
+            .collect::<Vec<_>>();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(points, expected);
+
+        let grid_rows = grid
+            .rows()
+            .map(|r| r.map(Option::unwrap).cloned().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            grid_rows,
+            rows.iter().map(|r| r.to_vec()).collect::<Vec<_>>()
+        );
+        let first_column = grid.columns().next().unwrap();
+        assert_eq!(
+            first_column.rev().map(Option::unwrap).collect::<Vec<_>>(),
+            vec![&rows[2][0], &rows[1][0], &rows[0][0]]
+        );
+        assert_eq!(grid.columns().count(), 4);
+
+        let coord = grid.coordinate(1, 2);
+        *grid.at_mut(&coord).unwrap() = other.clone();
+        assert_eq!(grid.at(&coord), Some(&other));
+        grid.set(grid.coordinate(3, 0), other.clone());
+        assert_eq!(grid.rows().next().unwrap().last(), Some(Some(&other)));
+        assert!(grid.at_mut(&grid.coordinate(4, 0)).is_none());
+    }
+
+    #[test]
+    fn conformance_all_grids() {
+        let bits = [
+            [true, false, false, false],
+            [false, true, false, false],
+            [false, false, false, true],
+        ];
+        conformance::<BitGrid>(bits, true);
+        conformance::<ResizingBitGrid>(bits, true);
+        assert_eq!(
+            BitGrid::from_rows(bits)
+                .points()
+                .filter(|(_, v)| **v)
+                .count(),
+            3
+        );
+
+        let numbers = [[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11]];
+        conformance::<SingleVecGrid<u8>>(numbers, 99);
+        conformance::<VecGrid<u8>>(numbers, 99);
+        conformance::<SparseGrid<u8>>(numbers, 99);
+        conformance::<DynamicGrid<u8>>(numbers, 99);
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        let grid = SingleVecGrid::from_rows([[0, 1, 2], [3, 4, 5], [6, 7, 8]]);
+        let values = |n: Neighbours<'_, SingleVecGrid<u8>>| n.map(|(_, v)| *v).collect::<Vec<_>>();
+
+        assert_eq!(values(grid.von_neumann(&(1, 1))), vec![1, 5, 7, 3]);
+        assert_eq!(values(grid.von_neumann(&(0, 0))), vec![1, 3]);
+        assert_eq!(values(grid.moore(&(1, 1))), vec![1, 2, 5, 8, 7, 6, 3, 0]);
+        assert_eq!(values(grid.moore(&(2, 0))), vec![5, 4, 1]);
+        assert_eq!(
+            grid.wrapping_neighbours(&(0, 0), &VON_NEUMANN)
+                .collect::<Vec<_>>(),
+            vec![((0, 2), &6), ((1, 0), &1), ((0, 1), &3), ((2, 0), &2)]
+        );
+        let knight = [(1, 2), (2, 1), (-1, 2)];
+        assert_eq!(values(grid.neighbours(&(0, 0), &knight)), vec![7, 5]);
+
+        // Sparse grids skip cells without a value, and use their own coordinates
+        let mut sparse = SparseGrid::new();
+        sparse.set((-5, -5), 'a');
+        sparse.set((-4, -5), 'b');
+        sparse.set((-3, -3), 'c');
+        assert_eq!(
+            sparse.moore(&(-4, -4)).collect::<Vec<_>>(),
+            vec![((-4, -5), &'b'), ((-3, -3), &'c'), ((-5, -5), &'a')]
+        );
+        assert_eq!(
+            sparse
+                .wrapping_neighbours(&(-3, -3), &[(1, 1)])
+                .collect::<Vec<_>>(),
+            vec![((-5, -5), &'a')]
+        );
+        // Wraps to (-3, -5), which has no value
+        assert_eq!(sparse.wrapping_neighbours(&(-5, -5), &[(-1, 0)]).count(), 0);
+    }
+
+    #[test]
+    fn dynamic_grid_grows() {
+        let mut grid = DynamicGrid::new();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.at(&(0, 0)), None);
+
+        grid.set((2, 1), 1);
+        grid.set((-3, 4), 2);
+        grid.set((5, -2), 3);
+        assert_eq!(grid.bounds(), Some(((-3, -2), (5, 4))));
+        assert_eq!((grid.width(), grid.height()), (9, 7));
+        assert_eq!(grid.at(&(2, 1)), Some(&1));
+        assert_eq!(grid.at(&(-3, 4)), Some(&2));
+        assert_eq!(grid.at(&(5, -2)), Some(&3));
+        assert_eq!(grid.at(&(0, 0)), Some(&0));
+        assert_eq!(grid.at(&(6, 0)), None);
+        assert_eq!(grid.points().filter(|(_, v)| **v != 0).count(), 3);
+
+        let mut other = DynamicGrid::with_bounds((-3, -2), (5, 4));
+        other.set((5, -2), 3);
+        other.set((-3, 4), 2);
+        other.set((2, 1), 1);
+        assert_eq!(other, grid);
+    }
+
+    #[test]
+    fn sparse_lines_have_gaps() {
+        let mut grid = SparseGrid::new();
+        grid.set((-1, -1), 0);
+        grid.set((1, 0), 1);
+
+        assert_eq!(grid.coordinate(0, 0), (-1, -1));
+        let rows = grid
+            .rows()
+            .map(|r| r.collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            vec![vec![Some(&0), None, None], vec![None, None, Some(&1)]]
+        );
+        assert!(grid.in_bounds(&(0, -1)));
+        assert!(!grid.in_bounds(&(2, 0)));
+    }
+
+    #[test]
+    fn views() {
+        let grid = SingleVecGrid::from_rows([[1, 2, 3], [4, 5, 6]]);
+        let rows = |view: View<'_, SingleVecGrid<u8>>| {
+            view.rows()
+                .map(|r| r.map(|v| *v.unwrap()).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(rows(grid.view()), [[1, 2, 3], [4, 5, 6]]);
+        assert_eq!(rows(grid.view().transpose()), [[1, 4], [2, 5], [3, 6]]);
+        assert_eq!(rows(grid.view().flip_horizontal()), [[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(rows(grid.view().flip_vertical()), [[4, 5, 6], [1, 2, 3]]);
+        assert_eq!(rows(grid.view().rotate_half()), [[6, 5, 4], [3, 2, 1]]);
+        let clockwise = grid.view().rotate_clockwise();
+        assert_eq!(rows(clockwise), [[4, 1], [5, 2], [6, 3]]);
+        assert_eq!(
+            rows(grid.view().rotate_anticlockwise()),
+            [[3, 6], [2, 5], [1, 4]]
+        );
+        assert_eq!(
+            rows(
+                clockwise
+                    .rotate_clockwise()
+                    .rotate_clockwise()
+                    .rotate_clockwise()
+            ),
+            rows(grid.view())
+        );
+
+        // (0, 2) of the turned view is the bottom left of the grid
+        assert_eq!(clockwise.source(&(0, 2)), (2, 1));
+        assert_eq!(clockwise.at(&(2, 0)), None);
+
+        assert_eq!(rows(grid.view().crop(1, 0, 5, 1)), [[2, 3]]);
+        assert_eq!(rows(clockwise.crop(1, 1, 1, 2)), [[2], [3]]);
+        assert_eq!(grid.view().crop(3, 2, 1, 1).rows().count(), 0);
+
+        let copy: VecGrid<u8> = clockwise.crop(0, 1, 2, 2).to_grid();
+        assert_eq!(copy, VecGrid::from_rows([[5, 2], [6, 3]]));
+        let bits = BitGrid::from_rows([[true, false], [false, false]]);
+        let flipped: BitGrid = bits.view().flip_horizontal().to_grid();
+        assert_eq!(flipped.at(&(1, 0)), Some(&true));
+    }
+
+    #[test]
+    fn text_grids() {
+        let text = "S.#\n.#E\n..E\n";
+        let bit = |c| match c {
+            '#' => Ok(true),
+            '.' | 'S' | 'E' => Ok(false),
+            _ => Err(format!("unexpected {:?}", c)),
+        };
+
+        let parsed: TextGrid<BitGrid> = parse_text(text, &['S', 'E'], bit).unwrap();
+        assert_eq!(parsed.grid.set_cell_count(), 2);
+        assert_eq!(parsed.marker('S'), Some(&(0, 0)));
+        assert_eq!(parsed.markers('E').collect::<Vec<_>>(), [&(2, 1), &(2, 2)]);
+        assert_eq!(parsed.marker('x'), None);
+        assert_eq!(parsed.to_text(|v| v.cell_char()), text);
+
+        let chars: TextGrid<SingleVecGrid<char>> = parse_text(text, &[], Ok::<_, String>).unwrap();
+        assert_eq!(chars.grid.at(&(2, 1)), Some(&'E'));
+        assert_eq!(chars.to_text(|c| *c), text);
+
+        let sparse: TextGrid<SparseGrid<bool>> = parse_text(text, &['E'], bit).unwrap();
+        assert_eq!(sparse.marker('E'), Some(&(2, 1)));
+        assert_eq!(sparse.grid.at(&(1, 1)), Some(&true));
+
+        let err = parse_text::<SingleVecGrid<bool>, _>("..\n.x\n", &[], bit)
+            .map(|t| t.grid)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 2: unexpected 'x'"
+        );
+        let err = parse_text::<SingleVecGrid<bool>, _>("...\n.\n", &[], bit)
+            .map(|t| t.grid)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 2: row is 1 wide, expected 3"
+        );
+
+        let crlf: TextGrid<BitGrid> =
+            parse_text("S.#\r\n.#E\r\n..E\r\n\r\n", &['S', 'E'], bit).unwrap();
+        assert_eq!(crlf.grid.height(), 3);
+        assert_eq!(crlf.to_text(|v| v.cell_char()), text);
+    }
+
+    #[test]
+    fn diffs() {
+        let left = SingleVecGrid::from_rows(["#..", ".#.", "..#"].map(|r| r.chars()));
+        let mut right = SparseGrid::from_rows(["#..", ".#.", "..."].map(|r| r.chars()));
+        assert!(grid_diff(&left, &left).is_empty());
+        crate::assert_grid_eq!(left, left.clone());
+
+        right.set((1, 0), '#');
+        let diff = grid_diff(&left, &right);
+        assert_eq!(diff.differences(), &[(1, 0), (2, 2)]);
+        assert_eq!(
+            (diff.left_at(2, 2), diff.right_at(2, 2)),
+            (Some(&'#'), Some(&'.'))
+        );
+        assert_eq!(
+            diff.side_by_side(|c| *c),
+            "#.. | ##. <\n.#. | .#.\n..# | ... <\n"
+        );
+        assert_eq!(diff.overlay(|c| *c), "#!.\n.#.\n..!\n");
+
+        // A row the right grid doesn't have differs wherever the left has a cell
+        right.set((0, 3), '.');
+        let diff = grid_diff(&left, &right);
+        assert_eq!(diff.differences()[2..], [(0, 3)]);
+        assert!(diff
+            .report(|c| *c)
+            .starts_with("3 cells differ at (1, 0), (2, 2), (0, 3)\n"));
+    }
+
+    #[test]
+    #[should_panic(expected = "1 cells differ at (0, 1)")]
+    fn assert_grid_eq_fails() {
+        let left = BitGrid::from_rows([[true, false], [true, true]]);
+        let right = BitGrid::from_rows([[true, false], [false, true]]);
+        crate::assert_grid_eq!(left, right);
+    }
 
     #[test]
     fn empty_vecgrid() {
@@ -1,42 +1,143 @@
+use crate::error::AocError;
 use itertools::Itertools;
+use std::borrow::Cow;
 use std::env;
 use std::error::Error as StdError;
-use std::fmt::Debug;
+use std::fmt::{Debug, Write};
 use std::fs::File;
 use std::io::prelude::*;
-use std::io::{self, BufReader, Error as IoError};
+use std::io::{self, BufReader, Error as IoError, ErrorKind};
+use std::path::Path;
 use std::str::FromStr;
 use thiserror::Error;
 
+/// Where a line sits within a record-oriented input, both 1-based
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordPosition {
+    pub record: usize,
+    pub line: usize,
+}
+
 #[derive(Error, Debug)]
-pub enum ParseLinesError<L: StdError> {
+pub enum ParseLinesError<L: StdError + 'static> {
     #[error("IO Error reading from stream")]
     IoError(#[from] IoError),
-    #[error("Parser error")]
-    ParseError(L),
+    #[error("{}", snippet(*.line, .record, .text, .source))]
+    ParseError {
+        line: usize,
+        record: Option<RecordPosition>,
+        text: String,
+        source: L,
+    },
+    #[error("Parse error: {0}")]
+    InputError(L),
+}
+
+impl<L: StdError + 'static> ParseLinesError<L> {
+    fn parse(line: usize, record: Option<RecordPosition>, text: String, source: L) -> Self {
+        ParseLinesError::ParseError {
+            line,
+            record,
+            text,
+            source,
+        }
+    }
+}
+
+/// Renders the failing line with a caret under the column the source error reports, or
+/// under the whole line when it doesn't report one, e.g.
+///
+/// ```text
+/// Parse error on line 3 (record 2, line 1): invalid digit found in string
+///   |
+/// 3 | 12x
+///   | ^^^
+/// ```
+fn snippet(
+    line: usize,
+    record: &Option<RecordPosition>,
+    text: &str,
+    source: &(dyn StdError + 'static),
+) -> String {
+    let mut s = format!("Parse error on line {}", line);
+    if let Some(pos) = record {
+        write!(s, " (record {}, line {})", pos.record, pos.line).unwrap();
+    }
+    write!(s, ": {}", source).unwrap();
+
+    let gutter = " ".repeat(line.to_string().len());
+    let carets = match source_column(source) {
+        Some(column) => format!("{}^", " ".repeat(column.saturating_sub(1))),
+        None => "^".repeat(text.chars().count().max(1)),
+    };
+    write!(
+        s,
+        "\n{} |\n{} | {}\n{} | {}",
+        gutter, line, text, gutter, carets
+    )
+    .unwrap();
+    s
+}
+
+/// The 1-based column a parse error points at, for the errors that know one
+fn source_column(source: &(dyn StdError + 'static)) -> Option<usize> {
+    if let Some(AocError::Parse { column, .. }) = source.downcast_ref::<AocError>() {
+        Some(*column)
+    } else {
+        source
+            .downcast_ref::<peg::error::ParseError<peg::str::LineCol>>()
+            .map(|e| e.location.column)
+    }
+}
+
+impl<L> From<ParseLinesError<L>> for AocError
+where
+    L: StdError + Into<AocError> + 'static,
+{
+    fn from(e: ParseLinesError<L>) -> Self {
+        match e {
+            ParseLinesError::IoError(e) => e.into(),
+            ParseLinesError::ParseError { line, source, .. } => source.into().on_line(line),
+            ParseLinesError::InputError(e) => e.into(),
+        }
+    }
 }
 
-pub fn get_argv_reader() -> BufReader<Box<dyn Read>> {
+fn open_file(path: impl AsRef<Path>) -> Result<File, AocError> {
+    File::open(path.as_ref()).map_err(|e| match e.kind() {
+        ErrorKind::NotFound => AocError::MissingInput(path.as_ref().to_path_buf()),
+        _ => e.into(),
+    })
+}
+
+pub fn read_input(path: impl AsRef<Path>) -> Result<String, AocError> {
+    let mut s = String::new();
+    open_file(path)?.read_to_string(&mut s)?;
+    Ok(s)
+}
+
+pub fn get_argv_reader() -> Result<BufReader<Box<dyn Read>>, AocError> {
     let read: Box<dyn Read> = match env::args().nth(1) {
-        Some(path) => Box::new(File::open(path).expect("File")),
+        Some(path) => Box::new(open_file(path)?),
         None => Box::new(io::stdin()),
     };
-    BufReader::new(read)
+    Ok(BufReader::new(read))
 }
 
-pub fn load_argv_lines<T>() -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>
+pub fn load_argv_lines<T>(
+) -> Result<impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>, AocError>
 where
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    let reader = get_argv_reader();
-    parse_lines(reader.lines())
+    let reader = get_argv_reader()?;
+    Ok(parse_lines(reader.lines()))
 }
 
 pub fn load_stdin_lines<T>() -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>
 where
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
     let file = io::stdin();
     let reader = BufReader::new(file);
@@ -45,38 +146,328 @@ where
 
 pub fn load_file_lines<T>(
     path: &str,
-) -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>
+) -> Result<impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>, AocError>
 where
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    let file = File::open(path).expect("Could not open input file");
-    let reader = BufReader::new(file);
-    parse_lines(reader.lines())
+    let reader = BufReader::new(open_file(path)?);
+    Ok(parse_lines(reader.lines()))
 }
 
 pub fn load_argv_records<T>(
     end_of_record: &str,
-) -> impl Iterator<Item = Result<Vec<T>, ParseLinesError<<T as FromStr>::Err>>>
+) -> Result<impl Iterator<Item = Result<Vec<T>, ParseLinesError<<T as FromStr>::Err>>>, AocError>
 where
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    let reader = get_argv_reader();
-    parse_records(reader.lines(), end_of_record.to_string())
+    let reader = get_argv_reader()?;
+    Ok(parse_records(reader.lines(), end_of_record.to_string()))
 }
 
 pub fn load_file_records<T>(
     path: &str,
     end_of_record: &str,
-) -> impl Iterator<Item = Result<Vec<T>, ParseLinesError<<T as FromStr>::Err>>>
+) -> Result<impl Iterator<Item = Result<Vec<T>, ParseLinesError<<T as FromStr>::Err>>>, AocError>
 where
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    let file = File::open(path).expect("Could not open input file");
-    let reader = BufReader::new(file);
-    parse_records(reader.lines(), end_of_record.to_string())
+    let reader = BufReader::new(open_file(path)?);
+    Ok(parse_records(reader.lines(), end_of_record.to_string()))
+}
+
+/// Converts CRLF line endings to LF and ends non-empty input with exactly one newline,
+/// so grammars only have to handle a single layout. Input already in that shape is
+/// borrowed rather than copied.
+pub fn normalise_input(input: &str) -> Cow<'_, str> {
+    let ends_once = input.is_empty() || input.trim_end_matches('\n').len() + 1 == input.len();
+    if ends_once && !input.contains("\r\n") {
+        return Cow::Borrowed(input);
+    }
+    let mut s = input.replace("\r\n", "\n");
+    s.truncate(s.trim_end_matches('\n').len());
+    if !s.is_empty() {
+        s.push('\n');
+    }
+    Cow::Owned(s)
+}
+
+fn read_normalised(mut reader: impl Read) -> Result<String, AocError> {
+    let mut s = String::new();
+    reader.read_to_string(&mut s)?;
+    let normalised = match normalise_input(&s) {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(normalised) => Some(normalised),
+    };
+    Ok(normalised.unwrap_or(s))
+}
+
+pub fn load_argv_input() -> Result<String, AocError> {
+    read_normalised(get_argv_reader()?)
+}
+
+pub fn load_stdin_input() -> Result<String, AocError> {
+    read_normalised(io::stdin())
+}
+
+pub fn load_file_input(path: impl AsRef<Path>) -> Result<String, AocError> {
+    read_normalised(open_file(path)?)
+}
+
+/// Like `FromStr`, but the parsed value may borrow from the text it was parsed from.
+/// Every `FromStr` type gets this for free.
+pub trait FromBorrowedStr<'a>: Sized {
+    type Err;
+
+    fn from_borrowed_str(s: &'a str) -> Result<Self, Self::Err>;
+}
+
+impl<'a, T: FromStr> FromBorrowedStr<'a> for T {
+    type Err = <T as FromStr>::Err;
+
+    fn from_borrowed_str(s: &'a str) -> Result<Self, Self::Err> {
+        s.parse()
+    }
+}
+
+/// An input read into memory once, so lines can be handed out as `&str` instead of
+/// allocating a `String` per line like `BufRead::lines()` does
+#[derive(Debug, Clone)]
+pub struct InputBuffer {
+    text: String,
+}
+
+impl InputBuffer {
+    pub fn from_argv() -> Result<Self, AocError> {
+        Ok(InputBuffer {
+            text: load_argv_input()?,
+        })
+    }
+
+    pub fn from_stdin() -> Result<Self, AocError> {
+        Ok(InputBuffer {
+            text: load_stdin_input()?,
+        })
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AocError> {
+        Ok(InputBuffer {
+            text: load_file_input(path)?,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    pub fn parse_lines<'a, T>(
+        &'a self,
+    ) -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromBorrowedStr<'a>>::Err>>> + 'a
+    where
+        T: FromBorrowedStr<'a> + 'a,
+        <T as FromBorrowedStr<'a>>::Err: StdError + 'static,
+    {
+        parse_borrowed_lines(&self.text)
+    }
+}
+
+/// Parses each line of `input` without copying it. Only a failing line is copied, into
+/// its error.
+pub fn parse_borrowed_lines<'a, T>(
+    input: &'a str,
+) -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromBorrowedStr<'a>>::Err>>> + 'a
+where
+    T: FromBorrowedStr<'a> + 'a,
+    <T as FromBorrowedStr<'a>>::Err: StdError + 'static,
+{
+    input.lines().enumerate().map(|(i, l)| {
+        T::from_borrowed_str(l).map_err(|e| ParseLinesError::parse(i + 1, None, l.to_string(), e))
+    })
+}
+
+/// Parses a whole input in one go, after normalising it
+pub fn parse_input<T>(input: &str) -> Result<T, ParseLinesError<<T as FromStr>::Err>>
+where
+    T: FromStr,
+    <T as FromStr>::Err: StdError + 'static,
+{
+    parse_input_with(input, str::parse)
+}
+
+/// Like `parse_input`, but with any parser function, such as a peg grammar rule
+pub fn parse_input_with<T, E, F>(input: &str, parser: F) -> Result<T, ParseLinesError<E>>
+where
+    E: StdError + 'static,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    parser(&normalise_input(input)).map_err(ParseLinesError::InputError)
+}
+
+/// A blank-line separated block of an input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// 1-based position of the section in the input
+    pub index: usize,
+    /// 1-based line number the section starts on
+    pub line: usize,
+    /// The section's lines, without the final newline
+    pub text: &'a str,
+}
+
+#[derive(Error, Debug)]
+#[error("Error in section {section}: {source}")]
+pub struct SectionError {
+    pub section: usize,
+    pub source: AocError,
+}
+
+impl From<SectionError> for AocError {
+    fn from(e: SectionError) -> Self {
+        e.source
+    }
+}
+
+impl<'a> Section<'a> {
+    pub fn parse<T>(&self) -> Result<T, SectionError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Into<AocError>,
+    {
+        self.parse_with(str::parse)
+    }
+
+    /// Parses the section, reporting failures against lines of the whole input
+    pub fn parse_with<T, E, F>(&self, parser: F) -> Result<T, SectionError>
+    where
+        E: Into<AocError>,
+        F: FnOnce(&'a str) -> Result<T, E>,
+    {
+        parser(self.text).map_err(|e| SectionError {
+            section: self.index,
+            source: e.into().on_line(self.line),
+        })
+    }
+}
+
+fn trim_newline(s: &str) -> &str {
+    s.trim_end_matches(&['\r', '\n'][..])
+}
+
+/// Splits an input into sections on runs of blank lines. Expects normalised input.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut offset = 0;
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim_end().is_empty() {
+            if let Some((line, from)) = start.take() {
+                sections.push(Section {
+                    index: sections.len() + 1,
+                    line,
+                    text: trim_newline(&input[from..offset]),
+                });
+            }
+        } else if start.is_none() {
+            start = Some((i + 1, offset));
+        }
+        offset += line.len();
+    }
+    if let Some((line, from)) = start {
+        sections.push(Section {
+            index: sections.len() + 1,
+            line,
+            text: trim_newline(&input[from..]),
+        });
+    }
+    sections
+}
+
+fn section_count_error(input: &str, sections: &[Section], expected: usize) -> SectionError {
+    let (section, line, message) = match sections.get(expected) {
+        Some(extra) => (extra.index, extra.line, "unexpected extra section"),
+        None => (
+            sections.len() + 1,
+            input.lines().count() + 1,
+            "missing section",
+        ),
+    };
+    SectionError {
+        section,
+        source: AocError::parse(line, 1, message),
+    }
+}
+
+/// Parses an input made of a header section of one type followed by a body of another
+pub fn parse_two_sections<A, B>(input: &str) -> Result<(A, B), SectionError>
+where
+    A: FromStr,
+    B: FromStr,
+    <A as FromStr>::Err: Into<AocError>,
+    <B as FromStr>::Err: Into<AocError>,
+{
+    parse_two_sections_with(input, str::parse, str::parse)
+}
+
+pub fn parse_two_sections_with<A, B, EA, EB, FA, FB>(
+    input: &str,
+    first: FA,
+    second: FB,
+) -> Result<(A, B), SectionError>
+where
+    EA: Into<AocError>,
+    EB: Into<AocError>,
+    FA: FnOnce(&str) -> Result<A, EA>,
+    FB: FnOnce(&str) -> Result<B, EB>,
+{
+    let input = normalise_input(input);
+    let sections = sections(&input);
+    if sections.len() != 2 {
+        return Err(section_count_error(&input, &sections, 2));
+    }
+    Ok((
+        sections[0].parse_with(first)?,
+        sections[1].parse_with(second)?,
+    ))
+}
+
+/// Parses every blank-line separated block of an input as the same type
+pub fn parse_blocks<T>(input: &str) -> Result<Vec<T>, SectionError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<AocError>,
+{
+    parse_blocks_with(input, str::parse)
+}
+
+pub fn parse_blocks_with<T, E, F>(input: &str, mut parser: F) -> Result<Vec<T>, SectionError>
+where
+    E: Into<AocError>,
+    F: FnMut(&str) -> Result<T, E>,
+{
+    let input = normalise_input(input);
+    sections(&input)
+        .iter()
+        .map(|section| section.parse_with(&mut parser))
+        .collect()
+}
+
+/// Parses every line of an in-memory input, reporting failures against their line number
+pub fn parse_str_lines<T>(input: &str) -> Result<Vec<T>, AocError>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Into<AocError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| l.parse().map_err(|e: T::Err| e.into().on_line(i + 1)))
+        .collect()
 }
 
 pub fn parse_lines<T, I>(
@@ -85,12 +476,12 @@ pub fn parse_lines<T, I>(
 where
     I: Iterator<Item = Result<String, std::io::Error>>,
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    input.map(|line| match line {
+    input.enumerate().map(|(i, line)| match line {
         Ok(l) => match l.parse() {
             Ok(t) => Ok(t),
-            Err(e) => Err(ParseLinesError::ParseError(e)),
+            Err(e) => Err(ParseLinesError::parse(i + 1, None, l, e)),
         },
         Err(e) => Err(e.into()),
     })
@@ -103,11 +494,13 @@ pub fn parse_records<T, I>(
 where
     I: Iterator<Item = Result<String, std::io::Error>> + Itertools,
     T: FromStr,
-    <T as FromStr>::Err: StdError,
+    <T as FromStr>::Err: StdError + 'static,
 {
-    input.batching(move |it| {
+    let mut record = 0;
+    input.enumerate().batching(move |it| {
         let mut batch = Vec::new();
-        for res in it {
+        record += 1;
+        for (i, res) in it {
             if let Err(e) = res {
                 return Some(Err(e.into()));
             }
@@ -118,7 +511,18 @@ where
 
             match line_string.parse() {
                 Ok(t) => batch.push(t),
-                Err(e) => return Some(Err(ParseLinesError::ParseError(e))),
+                Err(e) => {
+                    let pos = RecordPosition {
+                        record,
+                        line: batch.len() + 1,
+                    };
+                    return Some(Err(ParseLinesError::parse(
+                        i + 1,
+                        Some(pos),
+                        line_string,
+                        e,
+                    )));
+                }
             }
         }
         if batch.is_empty() {
@@ -141,4 +545,115 @@ mod tests {
         let output: Vec<u32> = parse_lines(cursor.lines()).map(|x| x.unwrap()).collect();
         assert_eq!(vec![1, 2, 3, 4], output);
     }
+
+    #[test]
+    fn whole_input() {
+        assert_eq!(normalise_input("a\r\nb\n\n\n"), "a\nb\n");
+        assert_eq!(normalise_input("a\nb"), "a\nb\n");
+        assert_eq!(normalise_input(""), "");
+        assert!(matches!(normalise_input("a\nb\n"), Cow::Borrowed("a\nb\n")));
+        assert!(matches!(normalise_input("a\n\n"), Cow::Owned(_)));
+
+        let lines = parse_input_with(
+            "x\r\ny",
+            |s| -> Result<Vec<String>, std::convert::Infallible> {
+                Ok(s.split_terminator('\n').map(String::from).collect())
+            },
+        );
+        assert_eq!(lines.unwrap(), vec!["x", "y"]);
+        assert!(matches!(
+            parse_input::<u32>("4x\n"),
+            Err(ParseLinesError::InputError(_))
+        ));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Pair<'a>(&'a str, &'a str);
+
+    impl<'a> FromBorrowedStr<'a> for Pair<'a> {
+        type Err = AocError;
+
+        fn from_borrowed_str(s: &'a str) -> Result<Self, Self::Err> {
+            let (l, r) = s
+                .split_once('=')
+                .ok_or_else(|| AocError::parse(1, s.len() + 1, "expected ="))?;
+            Ok(Pair(l, r))
+        }
+    }
+
+    #[test]
+    fn borrowed_lines() {
+        let input = "a=b\r\nc=d\n";
+        let pairs = parse_borrowed_lines::<Pair>(input)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(pairs, vec![Pair("a", "b"), Pair("c", "d")]);
+
+        let numbers = parse_borrowed_lines::<u32>("1\n2\n")
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(numbers, vec![1, 2]);
+
+        match parse_borrowed_lines::<Pair>("a=b\nc\n").nth(1) {
+            Some(Err(ParseLinesError::ParseError { line, text, .. })) => {
+                assert_eq!((line, text.as_str()), (2, "c"))
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+
+        // The caret sits under the column the error reports
+        let err = parse_borrowed_lines::<Pair>("a=b\nabc\n")
+            .nth(1)
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error on line 2: Parse error at line 1, column 4: expected =\n  |\n2 | abc\n  |    ^"
+        );
+    }
+
+    #[test]
+    fn sectioned_input() {
+        let input = "a\nb\n\n\n1\n2\n";
+        let found = sections(input);
+        assert_eq!(found.len(), 2);
+        assert_eq!(
+            (found[1].index, found[1].line, found[1].text),
+            (2, 5, "1\n2")
+        );
+
+        let (header, body): (u8, u32) = parse_two_sections("7\r\n\r\n42").unwrap();
+        assert_eq!((header, body), (7, 42));
+
+        let err = parse_two_sections::<u8, u32>("7\n\n4x\n").unwrap_err();
+        assert_eq!(err.section, 2);
+        match err.source {
+            AocError::Parse { line, .. } => assert_eq!(line, 3),
+            other => panic!("Unexpected error {:?}", other),
+        }
+        assert_eq!(parse_two_sections::<u8, u8>("7\n").unwrap_err().section, 2);
+
+        let blocks = parse_blocks_with("a\nb\n\nc", |s| Ok::<_, AocError>(s.to_string())).unwrap();
+        assert_eq!(blocks, vec!["a\nb", "c"]);
+    }
+
+    #[test]
+    fn record_error_position() {
+        let cursor = Cursor::new("1\n2\n\n3\n4x\n".to_string());
+        let err = parse_records::<u32, _>(cursor.lines(), "".to_string())
+            .find_map(|r| r.err())
+            .unwrap();
+
+        match &err {
+            ParseLinesError::ParseError { line, record, .. } => {
+                assert_eq!(*line, 5);
+                assert_eq!(*record, Some(RecordPosition { record: 2, line: 2 }));
+            }
+            other => panic!("Unexpected error {:?}", other),
+        }
+        assert_eq!(
+            err.to_string(),
+            "Parse error on line 5 (record 2, line 2): invalid digit found in string\n  |\n5 | 4x\n  | ^^"
+        );
+    }
 }
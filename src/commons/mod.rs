@@ -1,4 +1,8 @@
 pub mod geom;
 pub mod grid;
 pub mod io;
+pub mod ocr;
+pub mod pathfinding;
+pub mod regions;
+pub mod render;
 pub mod test_helpers;
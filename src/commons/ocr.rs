@@ -0,0 +1,341 @@
+//! Reads the block letters some puzzles draw their answers in. Two fonts are known: one 6
+//! pixels tall, mostly 4 wide, and one 10 pixels tall and 6 wide. The font is picked by the
+//! height of the lit area and letters are split on blank columns, or at the font's pitch
+//! where a wide letter runs into the next one, so the spacing needn't be given.
+
+use super::grid::Grid;
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Unverified: M, Q, T, W and X haven't turned up in an answer yet, so they're drawn in the
+/// style of the others and may not match the puzzle's.
+const SMALL_FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('D', ["###.", "#..#", "#..#", "#..#", "#..#", "###."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', ["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('M', ["#...#", "##.##", "#.#.#", "#...#", "#...#", "#...#"]),
+    ('N', ["#..#", "#..#", "##.#", "#.##", "#..#", "#..#"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('Q', [".##.", "#..#", "#..#", "#..#", "#.##", ".###"]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('T', ["#####", "..#..", "..#..", "..#..", "..#..", "..#.."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('V', ["#...#", "#...#", "#...#", ".#.#.", ".#.#.", "..#.."]),
+    ('W', ["#...#", "#...#", "#...#", "#.#.#", "##.##", "#...#"]),
+    ('X', ["#...#", ".#.#.", "..#..", "..#..", ".#.#.", "#...#"]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const LARGE_FONT: &[(char, [&str; 10])] = &[
+    (
+        'A',
+        [
+            "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'B',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+            "#....#", "#####.",
+        ],
+    ),
+    (
+        'C',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#....#", ".####.",
+        ],
+    ),
+    (
+        'E',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'F',
+        [
+            "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'G',
+        [
+            ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+            "#...##", ".###.#",
+        ],
+    ),
+    (
+        'H',
+        [
+            "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'J',
+        [
+            "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+            "#...#.", ".###..",
+        ],
+    ),
+    (
+        'K',
+        [
+            "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+            "#...#.", "#....#",
+        ],
+    ),
+    (
+        'L',
+        [
+            "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+    (
+        'N',
+        [
+            "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+            "#...##", "#....#",
+        ],
+    ),
+    (
+        'P',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+            "#.....", "#.....",
+        ],
+    ),
+    (
+        'R',
+        [
+            "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'X',
+        [
+            "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+            "#....#", "#....#",
+        ],
+    ),
+    (
+        'Z',
+        [
+            "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+            "#.....", "######",
+        ],
+    ),
+];
+
+/// Columns from the start of one small letter to the start of the next
+const SMALL_PITCH: usize = 5;
+/// Columns from the start of one large letter to the start of the next
+const LARGE_PITCH: usize = 8;
+
+struct Font {
+    /// Glyphs keyed by their rows joined with newlines
+    glyphs: HashMap<String, char>,
+    /// The widest glyph
+    width: usize,
+    /// Columns from the start of one letter to the start of the next
+    pitch: usize,
+}
+
+lazy_static! {
+    /// The fonts keyed by their height
+    static ref FONTS: HashMap<usize, Font> = {
+        let mut fonts = HashMap::new();
+        fonts.insert(6, index(SMALL_FONT, SMALL_PITCH));
+        fonts.insert(10, index(LARGE_FONT, LARGE_PITCH));
+        fonts
+    };
+}
+
+fn index<const H: usize>(font: &[(char, [&str; H])], pitch: usize) -> Font {
+    Font {
+        glyphs: font.iter().map(|(c, rows)| (rows.join("\n"), *c)).collect(),
+        width: font
+            .iter()
+            .map(|(_, rows)| rows[0].len())
+            .max()
+            .unwrap_or(0),
+        pitch,
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum OcrError {
+    #[error("No font is {height} pixels tall:\n{bitmap}")]
+    UnknownFont { height: usize, bitmap: String },
+    #[error("Unrecognised glyph at letter {position}:\n{bitmap}")]
+    UnknownGlyph { position: usize, bitmap: String },
+}
+
+fn draw(pixels: &[Vec<bool>], columns: std::ops::Range<usize>) -> String {
+    pixels
+        .iter()
+        .map(|row| {
+            row[columns.clone()]
+                .iter()
+                .map(|lit| if *lit { '#' } else { '.' })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads the letters drawn in the lit cells of `grid`
+pub fn decode<G: Grid<Value = bool>>(grid: &G) -> Result<String, OcrError> {
+    let mut pixels = grid
+        .rows()
+        .map(|row| row.map(|v| v.copied().unwrap_or(false)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    // Crop to the rows between the first and last lit ones
+    let lit = |row: &Vec<bool>| row.contains(&true);
+    let (top, bottom) = match (pixels.iter().position(lit), pixels.iter().rposition(lit)) {
+        (Some(top), Some(bottom)) => (top, bottom),
+        _ => return Ok(String::new()),
+    };
+    pixels.truncate(bottom + 1);
+    pixels.drain(..top);
+    let width = pixels[0].len();
+    let font = FONTS
+        .get(&pixels.len())
+        .ok_or_else(|| OcrError::UnknownFont {
+            height: pixels.len(),
+            bitmap: draw(&pixels, 0..width),
+        })?;
+
+    let lit_column = |x: usize| pixels.iter().any(|row| row[x]);
+    let mut text = String::new();
+    let mut x = 0;
+    while x < width {
+        if !lit_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && lit_column(x) {
+            x += 1;
+        }
+        // A letter as wide as the pitch leaves no gap before the next, so a run wider than
+        // any glyph is cut at the pitch
+        let mut from = start;
+        while from < x {
+            let to = if x - from > font.width {
+                (from + font.pitch).min(x)
+            } else {
+                x
+            };
+            let glyph = draw(&pixels, from..to);
+            match font.glyphs.get(&glyph) {
+                Some(c) => text.push(*c),
+                None => {
+                    return Err(OcrError::UnknownGlyph {
+                        position: text.len() + 1,
+                        bitmap: glyph,
+                    })
+                }
+            }
+            from = to;
+        }
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::grid::{BitGrid, SparseGrid};
+
+    fn grid(rows: &[&str]) -> BitGrid {
+        BitGrid::from_rows(rows.iter().map(|r| r.chars().map(|c| c == '#')))
+    }
+
+    #[test]
+    fn small_font() {
+        let text = grid(&[
+            ".......................................",
+            "###..#....###...##..####.###...##..#...",
+            "#..#.#....#..#.#..#.#....#..#.#..#.#...",
+            "#..#.#....#..#.#..#.###..###..#....#...",
+            "###..#....###..####.#....#..#.#....#...",
+            "#....#....#....#..#.#....#..#.#..#.#...",
+            "#....####.#....#..#.#....###...##..####",
+        ]);
+        assert_eq!(decode(&text), Ok("PLPAFBCL".to_string()));
+
+        // Every letter reads back at the puzzle's pitch, where the 5 wide ones touch the
+        // letter after them
+        let mut all = SparseGrid::new();
+        for (i, (_, rows)) in SMALL_FONT.iter().enumerate() {
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    all.set(((i * SMALL_PITCH + x) as isize, y as isize), c == '#');
+                }
+            }
+        }
+        assert_eq!(decode(&all), Ok("ABCDEFGHIJKLMNOPQRSTUVWXYZ".to_string()));
+
+        let wide = grid(&[
+            "#...#######...##...##...##...#",
+            "##.##..#..#...##...#.#.#.#...#",
+            "#.#.#..#..#...##...#..#...#.#.",
+            "#...#..#...#.#.#.#.#..#....#..",
+            "#...#..#...#.#.##.##.#.#...#..",
+            "#...#..#....#..#...##...#..#..",
+        ]);
+        assert_eq!(decode(&wide), Ok("MTVWXY".to_string()));
+    }
+
+    #[test]
+    fn large_font() {
+        let mut text = SparseGrid::new();
+        for (i, letter) in ['H', 'X', 'Z'].iter().enumerate() {
+            let rows = LARGE_FONT.iter().find(|(c, _)| c == letter).unwrap().1;
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    text.set(((i * 8 + x) as isize, y as isize), c == '#');
+                }
+            }
+        }
+        assert_eq!(decode(&text), Ok("HXZ".to_string()));
+    }
+
+    #[test]
+    fn unknown_glyphs() {
+        let err = decode(&grid(&[
+            ".##..#.#", "#..#..#.", "#..#.#.#", "####..#.", "#..#.#.#", "#..#..#.",
+        ]))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Unrecognised glyph at letter 2:\n#.#\n.#.\n#.#\n.#.\n#.#\n.#."
+        );
+
+        assert!(matches!(
+            decode(&grid(&["##", "##"])),
+            Err(OcrError::UnknownFont { height: 2, .. })
+        ));
+    }
+}
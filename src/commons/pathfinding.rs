@@ -0,0 +1,336 @@
+use super::grid::{Grid, VON_NEUMANN};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+/// A cell's coordinate and value, as passed to step and goal closures
+pub type Cell<'a, G> = (&'a <G as Grid>::Coordinate, &'a <G as Grid>::Value);
+
+/// Searches over the cells of a grid, stepping between the neighbours picked out by a
+/// stencil. Which steps are allowed, and what they cost, is decided by a closure given the
+/// cell stepped from and the cell stepped to.
+///
+/// ```ignore
+/// let climbable = |(_, from): Cell<'_, _>, (_, to): Cell<'_, _>| *to <= from + 1;
+/// let paths = Search::new(&heights).bfs(starts, climbable, |(c, _)| *c == end);
+/// ```
+pub struct Search<'a, G> {
+    grid: &'a G,
+    stencil: &'a [(isize, isize)],
+    reverse: bool,
+}
+
+/// What a search found: the cheapest cost to each cell it settled, and the way there
+pub struct Paths<C> {
+    costs: HashMap<C, usize>,
+    previous: HashMap<C, C>,
+    goal: Option<C>,
+    reverse: bool,
+}
+
+impl<C: Clone + Eq + Hash> Paths<C> {
+    fn new(reverse: bool) -> Self {
+        Paths {
+            costs: HashMap::new(),
+            previous: HashMap::new(),
+            goal: None,
+            reverse,
+        }
+    }
+
+    /// The first goal cell reached, if any
+    pub fn goal(&self) -> Option<&C> {
+        self.goal.as_ref()
+    }
+
+    pub fn goal_cost(&self) -> Option<usize> {
+        self.goal.as_ref().and_then(|g| self.cost(g))
+    }
+
+    /// The cost of the cheapest path from any start to `coord`, if the search got that far
+    pub fn cost(&self, coord: &C) -> Option<usize> {
+        self.costs.get(coord).copied()
+    }
+
+    /// The cells along the cheapest path between a start and `coord`, both included. For a
+    /// reverse search the path runs from `coord` to the start, which is the direction the
+    /// steps were checked in.
+    pub fn path_to(&self, coord: &C) -> Option<Vec<C>> {
+        self.costs.get(coord)?;
+        let mut path = vec![coord.clone()];
+        while let Some(prev) = self.previous.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        if !self.reverse {
+            path.reverse();
+        }
+        Some(path)
+    }
+
+    /// Every cell the search settled, in no particular order
+    pub fn reached(&self) -> impl Iterator<Item = &C> + '_ {
+        self.costs.keys()
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<C>> {
+        self.goal.as_ref().and_then(|g| self.path_to(g))
+    }
+}
+
+struct Queued<C> {
+    priority: usize,
+    cost: usize,
+    coord: C,
+}
+
+impl<C> PartialEq for Queued<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<C> Eq for Queued<C> {}
+
+impl<C> PartialOrd for Queued<C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<C> Ord for Queued<C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Reverse(self.priority).cmp(&Reverse(other.priority))
+    }
+}
+
+impl<'a, G> Search<'a, G>
+where
+    G: Grid,
+    G::Coordinate: Clone + Eq + Hash,
+{
+    /// A search stepping to the four orthogonal neighbours
+    pub fn new(grid: &'a G) -> Self {
+        Search {
+            grid,
+            stencil: &VON_NEUMANN,
+            reverse: false,
+        }
+    }
+
+    pub fn stencil(self, stencil: &'a [(isize, isize)]) -> Self {
+        Search { stencil, ..self }
+    }
+
+    /// Walks the steps backwards: moving from `a` to `b` is allowed when stepping from `b`
+    /// to `a` is. Handy for searching from a single goal towards many possible starts.
+    pub fn reverse(self) -> Self {
+        Search {
+            reverse: true,
+            ..self
+        }
+    }
+
+    fn step_cost<F>(&self, cost: &mut F, from: Cell<'_, G>, to: Cell<'_, G>) -> Option<usize>
+    where
+        F: FnMut(Cell<'_, G>, Cell<'_, G>) -> Option<usize>,
+    {
+        if self.reverse {
+            cost(to, from)
+        } else {
+            cost(from, to)
+        }
+    }
+
+    /// Breadth first search where every allowed step costs 1. Stops at the first cell
+    /// `is_goal` accepts, or once everything reachable from `starts` has been seen.
+    pub fn bfs(
+        &self,
+        starts: impl IntoIterator<Item = G::Coordinate>,
+        mut passable: impl FnMut(Cell<'_, G>, Cell<'_, G>) -> bool,
+        mut is_goal: impl FnMut(Cell<'_, G>) -> bool,
+    ) -> Paths<G::Coordinate> {
+        let mut paths = Paths::new(self.reverse);
+        let mut queue = VecDeque::new();
+        for start in starts {
+            if self.grid.at(&start).is_some() && !paths.costs.contains_key(&start) {
+                paths.costs.insert(start.clone(), 0);
+                queue.push_back(start);
+            }
+        }
+
+        let mut cost = |from: Cell<'_, G>, to: Cell<'_, G>| passable(from, to).then_some(1);
+        while let Some(coord) = queue.pop_front() {
+            let value = self.grid.at(&coord).unwrap();
+            if is_goal((&coord, value)) {
+                paths.goal = Some(coord);
+                break;
+            }
+            let next_cost = paths.costs[&coord] + 1;
+            for (neighbour, neighbour_value) in self.grid.neighbours(&coord, self.stencil) {
+                if paths.costs.contains_key(&neighbour)
+                    || self
+                        .step_cost(&mut cost, (&coord, value), (&neighbour, neighbour_value))
+                        .is_none()
+                {
+                    continue;
+                }
+                paths.costs.insert(neighbour.clone(), next_cost);
+                paths.previous.insert(neighbour.clone(), coord.clone());
+                queue.push_back(neighbour);
+            }
+        }
+        paths
+    }
+
+    /// Cheapest paths where `cost` prices each step, `None` meaning it can't be taken
+    pub fn dijkstra(
+        &self,
+        starts: impl IntoIterator<Item = G::Coordinate>,
+        cost: impl FnMut(Cell<'_, G>, Cell<'_, G>) -> Option<usize>,
+        is_goal: impl FnMut(Cell<'_, G>) -> bool,
+    ) -> Paths<G::Coordinate> {
+        self.astar(starts, cost, |_| 0, is_goal)
+    }
+
+    /// Dijkstra's search guided towards the goal by `heuristic`. Cells aren't revisited once
+    /// settled, so for the result to be the cheapest path the heuristic must be consistent:
+    /// for every step from `a` to `b`, `heuristic(a) <= cost(a, b) + heuristic(b)`, and
+    /// zero at the goal. Manhattan distance on a grid where every step costs at least 1 is.
+    pub fn astar(
+        &self,
+        starts: impl IntoIterator<Item = G::Coordinate>,
+        mut cost: impl FnMut(Cell<'_, G>, Cell<'_, G>) -> Option<usize>,
+        mut heuristic: impl FnMut(&G::Coordinate) -> usize,
+        mut is_goal: impl FnMut(Cell<'_, G>) -> bool,
+    ) -> Paths<G::Coordinate> {
+        let mut paths = Paths::new(self.reverse);
+        // Best cost found so far to cells that haven't been settled yet
+        let mut tentative = HashMap::new();
+        let mut queue = BinaryHeap::new();
+        for start in starts {
+            if self.grid.at(&start).is_some() {
+                tentative.insert(start.clone(), 0);
+                queue.push(Queued {
+                    priority: heuristic(&start),
+                    cost: 0,
+                    coord: start,
+                });
+            }
+        }
+
+        while let Some(Queued {
+            cost: so_far,
+            coord,
+            ..
+        }) = queue.pop()
+        {
+            if paths.costs.contains_key(&coord) || tentative.get(&coord) != Some(&so_far) {
+                continue;
+            }
+            paths.costs.insert(coord.clone(), so_far);
+            let value = self.grid.at(&coord).unwrap();
+            if is_goal((&coord, value)) {
+                paths.goal = Some(coord);
+                break;
+            }
+
+            for (neighbour, neighbour_value) in self.grid.neighbours(&coord, self.stencil) {
+                if paths.costs.contains_key(&neighbour) {
+                    continue;
+                }
+                let step =
+                    match self.step_cost(&mut cost, (&coord, value), (&neighbour, neighbour_value))
+                    {
+                        Some(step) => step,
+                        None => continue,
+                    };
+                let next_cost = so_far + step;
+                if tentative.get(&neighbour).is_none_or(|&c| next_cost < c) {
+                    tentative.insert(neighbour.clone(), next_cost);
+                    paths.previous.insert(neighbour.clone(), coord.clone());
+                    queue.push(Queued {
+                        priority: next_cost + heuristic(&neighbour),
+                        cost: next_cost,
+                        coord: neighbour,
+                    });
+                }
+            }
+        }
+        paths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::grid::{SingleVecGrid, SparseGrid, MOORE};
+
+    fn maze() -> SingleVecGrid<char> {
+        SingleVecGrid::from_rows(
+            ["S.#....", ".##.##.", "...#...", ".#...#E"].map(|r| r.chars().collect::<Vec<_>>()),
+        )
+    }
+
+    fn open(_: Cell<'_, SingleVecGrid<char>>, (_, to): Cell<'_, SingleVecGrid<char>>) -> bool {
+        *to != '#'
+    }
+
+    #[test]
+    fn bfs_paths() {
+        let grid = maze();
+        let paths = Search::new(&grid).bfs([(0, 0)], open, |(_, v)| *v == 'E');
+        assert_eq!(paths.goal(), Some(&(6, 3)));
+        assert_eq!(paths.goal_cost(), Some(11));
+        let path = paths.goal_path().unwrap();
+        assert_eq!(path.len(), 12);
+        assert_eq!((path[0], path[11]), ((0, 0), (6, 3)));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+
+        // Diagonal steps cut through the walls' corners
+        let diagonal = Search::new(&grid)
+            .stencil(&MOORE)
+            .bfs([(0, 0)], open, |(_, v)| *v == 'E');
+        assert_eq!(diagonal.goal_cost(), Some(7));
+
+        let walled_in = Search::new(&grid).bfs([(0, 0)], |_, (_, to)| *to == '.', |_| false);
+        assert_eq!(walled_in.cost(&(6, 3)), None);
+        assert_eq!(walled_in.cost(&(4, 3)), Some(7));
+    }
+
+    #[test]
+    fn weighted_and_reverse() {
+        // Entering a cell costs its digit, so the cheap way round is longer
+        let grid = SingleVecGrid::from_rows([[1, 9, 1], [1, 9, 1], [1, 1, 1]]);
+        let enter = |_: Cell<'_, SingleVecGrid<u8>>, (_, to): Cell<'_, SingleVecGrid<u8>>| {
+            Some(*to as usize)
+        };
+        let search = Search::new(&grid);
+        let dijkstra = search.dijkstra([(0, 0)], enter, |(c, _)| *c == (2, 0));
+        assert_eq!(dijkstra.goal_cost(), Some(6));
+        let manhattan = |c: &(usize, usize)| 2 - c.0 + c.1;
+        let astar = search.astar([(0, 0)], enter, manhattan, |(c, _)| *c == (2, 0));
+        assert_eq!(astar.goal_cost(), Some(6));
+        assert_eq!(astar.goal_path(), dijkstra.goal_path());
+
+        // Reversed, the cost is of entering the cell stepped from
+        let reverse = Search::new(&grid)
+            .reverse()
+            .dijkstra([(2, 0)], enter, |(c, _)| *c == (0, 0));
+        assert_eq!(reverse.goal_cost(), Some(6));
+        let path = reverse.goal_path().unwrap();
+        assert_eq!((path[0], path[path.len() - 1]), ((0, 0), (2, 0)));
+    }
+
+    #[test]
+    fn multi_source_sparse() {
+        let mut grid = SparseGrid::new();
+        for x in -3..=3 {
+            grid.set((x, 0), x.unsigned_abs());
+        }
+        let paths = Search::new(&grid).bfs([(-3, 0), (3, 0)], |_, _| true, |(_, v)| *v == 0);
+        assert_eq!(paths.goal_cost(), Some(3));
+        assert_eq!(paths.cost(&(-1, 0)), Some(2));
+    }
+}
@@ -0,0 +1,277 @@
+//! Regions of connected cells: flood fill from a seed, labelling every connected component,
+//! and filling in from the edges to find what's enclosed. Which cells connect is decided by
+//! a stencil of offsets, as for `Grid::neighbours`.
+
+use super::grid::{Grid, VON_NEUMANN};
+use super::pathfinding::Search;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// A set of connected cells, with its size and extent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<C: Eq + Hash> {
+    pub cells: HashSet<C>,
+    /// Cell edges facing something outside the region, whether another cell or the edge of
+    /// the grid. Always counts the four orthogonal sides, whatever the stencil.
+    pub perimeter: usize,
+    /// Corners of the smallest rectangle holding the region
+    pub top_left: C,
+    pub bottom_right: C,
+}
+
+impl<C: Clone + Eq + Hash> Region<C> {
+    /// `None` for no cells, which have no bounding box
+    fn new<G: Grid<Coordinate = C>>(grid: &G, cells: HashSet<C>) -> Option<Self> {
+        let positions = cells.iter().map(|c| grid.position(c)).collect::<Vec<_>>();
+        let left = positions.iter().map(|p| p.0).min()?;
+        let right = positions.iter().map(|p| p.0).max()?;
+        let top = positions.iter().map(|p| p.1).min()?;
+        let bottom = positions.iter().map(|p| p.1).max()?;
+
+        let (width, height) = (grid.width() as isize, grid.height() as isize);
+        let perimeter = positions
+            .iter()
+            .flat_map(|(x, y)| VON_NEUMANN.iter().map(move |(dx, dy)| (x + dx, y + dy)))
+            .filter(|&(x, y)| {
+                !(0..width).contains(&x)
+                    || !(0..height).contains(&y)
+                    || !cells.contains(&grid.coordinate(x as usize, y as usize))
+            })
+            .count();
+
+        Some(Region {
+            top_left: grid.coordinate(left as usize, top as usize),
+            bottom_right: grid.coordinate(right as usize, bottom as usize),
+            cells,
+            perimeter,
+        })
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn contains(&self, coord: &C) -> bool {
+        self.cells.contains(coord)
+    }
+}
+
+/// Every cell reachable from `starts` through cells `inside` accepts
+fn fill<G>(
+    grid: &G,
+    starts: impl IntoIterator<Item = G::Coordinate>,
+    stencil: &[(isize, isize)],
+    mut inside: impl FnMut(&G::Value) -> bool,
+) -> HashSet<G::Coordinate>
+where
+    G: Grid,
+    G::Coordinate: Clone + Eq + Hash,
+{
+    let starts = starts
+        .into_iter()
+        .filter(|c| grid.at(c).is_some_and(&mut inside))
+        .collect::<Vec<_>>();
+    Search::new(grid)
+        .stencil(stencil)
+        .bfs(starts, |_, (_, to)| inside(to), |_| false)
+        .reached()
+        .cloned()
+        .collect()
+}
+
+/// The region of cells `inside` accepts that can be reached from `seed`, or `None` if the
+/// seed itself isn't inside
+pub fn flood_fill<G>(
+    grid: &G,
+    seed: G::Coordinate,
+    stencil: &[(isize, isize)],
+    inside: impl FnMut(&G::Value) -> bool,
+) -> Option<Region<G::Coordinate>>
+where
+    G: Grid,
+    G::Coordinate: Clone + Eq + Hash,
+{
+    Region::new(grid, fill(grid, [seed], stencil, inside))
+}
+
+/// The cells `open` accepts that can be reached from outside the grid, entering through
+/// the open cells on its edges. Open cells not in the region are enclosed.
+pub fn exterior<G>(
+    grid: &G,
+    stencil: &[(isize, isize)],
+    open: impl FnMut(&G::Value) -> bool,
+) -> Option<Region<G::Coordinate>>
+where
+    G: Grid,
+    G::Coordinate: Clone + Eq + Hash,
+{
+    let (width, height) = (grid.width(), grid.height());
+    let edges = (0..width)
+        .flat_map(|x| [(x, 0), (x, height.saturating_sub(1))])
+        .chain((0..height).flat_map(|y| [(0, y), (width.saturating_sub(1), y)]))
+        .map(|(x, y)| grid.coordinate(x, y));
+    Region::new(grid, fill(grid, edges, stencil, open))
+}
+
+/// The connected components of a grid, see `components`
+pub struct Components<C: Eq + Hash> {
+    labels: HashMap<C, usize>,
+    regions: Vec<Region<C>>,
+}
+
+impl<C: Eq + Hash> Components<C> {
+    /// Which region `coord` belongs to, as an index into `regions`
+    pub fn label(&self, coord: &C) -> Option<usize> {
+        self.labels.get(coord).copied()
+    }
+
+    pub fn regions(&self) -> &[Region<C>] {
+        &self.regions
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+}
+
+/// Labels every connected component of the grid. Neighbouring cells connect when `key`
+/// gives them the same key, and cells it gives `None` belong to no region. Regions are
+/// numbered in the order `Grid::points` first reaches them.
+///
+/// ```ignore
+/// // Islands of lit cells
+/// let islands = components(&bits, &VON_NEUMANN, |lit| lit.then_some(()));
+/// // Patches of equal values
+/// let patches = components(&heights, &MOORE, |h| Some(*h));
+/// ```
+pub fn components<G, K>(
+    grid: &G,
+    stencil: &[(isize, isize)],
+    mut key: impl FnMut(&G::Value) -> Option<K>,
+) -> Components<G::Coordinate>
+where
+    G: Grid,
+    G::Coordinate: Clone + Eq + Hash,
+    K: Eq,
+{
+    let mut components = Components {
+        labels: HashMap::new(),
+        regions: Vec::new(),
+    };
+    for (coord, value) in grid.points() {
+        if components.labels.contains_key(&coord) {
+            continue;
+        }
+        let region_key = match key(value) {
+            Some(k) => k,
+            None => continue,
+        };
+        let cells = fill(grid, [coord], stencil, |v| {
+            key(v).as_ref() == Some(&region_key)
+        });
+        let label = components.regions.len();
+        for cell in &cells {
+            components.labels.insert(cell.clone(), label);
+        }
+        components.regions.extend(Region::new(grid, cells));
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::grid::{BitGrid, SingleVecGrid, SparseGrid, MOORE};
+
+    fn bits(rows: &[&str]) -> BitGrid {
+        BitGrid::from_rows(rows.iter().map(|r| r.chars().map(|c| c == '#')))
+    }
+
+    #[test]
+    fn fills() {
+        let grid = bits(&["##...", "#....", ".##.."]);
+        let lit = |v: &bool| *v;
+
+        let corner = flood_fill(&grid, (0, 0), &VON_NEUMANN, lit).unwrap();
+        assert_eq!(corner.area(), 3);
+        assert_eq!(corner.perimeter, 8);
+        assert_eq!((corner.top_left, corner.bottom_right), ((0, 0), (1, 1)));
+        assert!(flood_fill(&grid, (2, 0), &VON_NEUMANN, lit).is_none());
+
+        // The two shapes only touch diagonally, cutting off the dark cell between them
+        assert_eq!(flood_fill(&grid, (0, 0), &MOORE, lit).unwrap().area(), 5);
+        assert_eq!(
+            flood_fill(&grid, (1, 2), &VON_NEUMANN, lit).unwrap().area(),
+            2
+        );
+        let dark = |v: &bool| !v;
+        assert_eq!(
+            flood_fill(&grid, (2, 0), &VON_NEUMANN, dark)
+                .unwrap()
+                .area(),
+            9
+        );
+        assert_eq!(
+            flood_fill(&grid, (0, 2), &VON_NEUMANN, dark)
+                .unwrap()
+                .area(),
+            1
+        );
+    }
+
+    #[test]
+    fn labels() {
+        let grid = bits(&["##..#", "#..#.", "...##"]);
+        let islands = components(&grid, &VON_NEUMANN, |v| v.then_some(()));
+        assert_eq!(islands.len(), 3);
+        assert_eq!(islands.label(&(0, 1)), Some(0));
+        assert_eq!(islands.label(&(4, 0)), Some(1));
+        assert_eq!(islands.label(&(4, 2)), Some(2));
+        assert_eq!(islands.label(&(2, 0)), None);
+        assert_eq!(
+            islands
+                .regions()
+                .iter()
+                .map(Region::area)
+                .collect::<Vec<_>>(),
+            [3, 1, 3]
+        );
+        assert_eq!(components(&grid, &MOORE, |v| v.then_some(())).len(), 2);
+
+        let heights = SingleVecGrid::from_rows([[1, 1, 2], [3, 1, 2], [3, 3, 2]]);
+        let patches = components(&heights, &VON_NEUMANN, |h| Some(*h));
+        assert_eq!(patches.len(), 3);
+        let twos = &patches.regions()[patches.label(&(2, 0)).unwrap()];
+        assert_eq!(twos.perimeter, 8);
+        assert_eq!((twos.top_left, twos.bottom_right), ((2, 0), (2, 2)));
+    }
+
+    #[test]
+    fn enclosed() {
+        let grid = bits(&["#####.", "#..#..", "#####.", "......"]);
+        let outside = exterior(&grid, &VON_NEUMANN, |v| !v).unwrap();
+        assert_eq!(outside.area(), 10);
+        assert!(!outside.contains(&(1, 1)));
+        let open = grid.points().filter(|(_, v)| !**v).count();
+        assert_eq!(open - outside.area(), 2);
+
+        // Sparse grids use their own coordinates
+        let mut sparse = SparseGrid::new();
+        for x in -2..=2 {
+            for y in -2..=2 {
+                sparse.set((x, y), x.abs().max(y.abs()) == 1);
+            }
+        }
+        let outside = exterior(&sparse, &VON_NEUMANN, |v| !v).unwrap();
+        assert_eq!(outside.area(), 16);
+        assert!(!outside.contains(&(0, 0)));
+        assert_eq!(outside.top_left, (-2, -2));
+        let ring = flood_fill(&sparse, (1, 1), &VON_NEUMANN, |v| *v).unwrap();
+        assert_eq!(ring.area(), 8);
+        assert_eq!(ring.perimeter, 16);
+    }
+}
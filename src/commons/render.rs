@@ -0,0 +1,206 @@
+//! Drawing grids as text, coloured terminal output or images. Each function takes a closure
+//! mapping a cell's value to what's drawn for it. Cells a sparse grid has no value for are
+//! left blank.
+
+use super::grid::Grid;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    pub fn grey(level: u8) -> Self {
+        Rgb(level, level, level)
+    }
+
+    /// Shades from dark blue at 0 to white at `max`, for heightmaps and the like
+    pub fn gradient(value: usize, max: usize) -> Self {
+        let t = value.min(max) as f64 / max.max(1) as f64;
+        let lerp = |from: f64| (from + (255.0 - from) * t).round() as u8;
+        Rgb(lerp(0.0), lerp(32.0), lerp(96.0))
+    }
+
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// How a value is drawn as text when no closure is given, as by `assert_grid_eq!`
+pub trait CellChar {
+    fn cell_char(&self) -> char;
+}
+
+impl CellChar for bool {
+    fn cell_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl CellChar for char {
+    fn cell_char(&self) -> char {
+        *self
+    }
+}
+
+/// One line of text per row
+pub fn to_string<G: Grid>(grid: &G, mut cell: impl FnMut(&G::Value) -> char) -> String {
+    let mut s = String::with_capacity((grid.width() + 1) * grid.height());
+    for row in grid.rows() {
+        s.extend(row.map(|v| v.map_or(' ', &mut cell)));
+        s.push('\n');
+    }
+    s
+}
+
+/// Like `to_string`, with each character in a 24-bit terminal colour
+pub fn to_ansi<G: Grid>(grid: &G, mut cell: impl FnMut(&G::Value) -> (char, Rgb)) -> String {
+    let mut s = String::new();
+    for row in grid.rows() {
+        let mut current = None;
+        for value in row {
+            match value.map(&mut cell) {
+                Some((c, colour)) => {
+                    if current != Some(colour) {
+                        write!(s, "\x1b[38;2;{};{};{}m", colour.0, colour.1, colour.2).unwrap();
+                        current = Some(colour);
+                    }
+                    s.push(c);
+                }
+                None => s.push(' '),
+            }
+        }
+        s.push_str("\x1b[0m\n");
+    }
+    s
+}
+
+/// Packed RGB bytes with each cell drawn as a `scale` pixel square, along with the image's
+/// width and height
+fn pixels<G: Grid>(
+    grid: &G,
+    mut colour: impl FnMut(&G::Value) -> Rgb,
+    scale: usize,
+) -> (usize, usize, Vec<u8>) {
+    let (width, height) = (grid.width() * scale, grid.height() * scale);
+    let mut data = Vec::with_capacity(width * height * 3);
+    for row in grid.rows() {
+        let mut line = Vec::with_capacity(width * 3);
+        for value in row {
+            let Rgb(r, g, b) = value.map_or(Rgb::BLACK, &mut colour);
+            for _ in 0..scale {
+                line.extend([r, g, b]);
+            }
+        }
+        for _ in 0..scale {
+            data.extend_from_slice(&line);
+        }
+    }
+    (width, height, data)
+}
+
+/// A binary PPM image, which most image viewers open
+pub fn to_ppm<G: Grid>(grid: &G, colour: impl FnMut(&G::Value) -> Rgb, scale: usize) -> Vec<u8> {
+    let (width, height, data) = pixels(grid, colour, scale);
+    let mut ppm = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    ppm.extend(data);
+    ppm
+}
+
+pub fn to_png<G: Grid>(
+    grid: &G,
+    colour: impl FnMut(&G::Value) -> Rgb,
+    scale: usize,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let (width, height, data) = pixels(grid, colour, scale);
+    let mut png = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(png)
+}
+
+/// An SVG with one unit square per cell, which stays sharp however far it's zoomed
+pub fn to_svg<G: Grid>(grid: &G, mut colour: impl FnMut(&G::Value) -> Rgb) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+        grid.width() * 8,
+        grid.height() * 8,
+        w = grid.width(),
+        h = grid.height(),
+    );
+    for (y, row) in grid.rows().enumerate() {
+        for (x, value) in row.enumerate() {
+            if let Some(value) = value {
+                writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"1\" height=\"1\" fill=\"{}\"/>",
+                    x,
+                    y,
+                    colour(value).hex()
+                )
+                .unwrap();
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::grid::{SingleVecGrid, SparseGrid};
+
+    fn heights() -> SingleVecGrid<u8> {
+        SingleVecGrid::from_rows([[0, 1, 2], [3, 4, 5]])
+    }
+
+    fn colour(h: &u8) -> Rgb {
+        Rgb::gradient(*h as usize, 5)
+    }
+
+    #[test]
+    fn text() {
+        let digit = |h: &u8| (b'0' + h) as char;
+        assert_eq!(to_string(&heights(), digit), "012\n345\n");
+
+        let mut sparse = SparseGrid::new();
+        sparse.set((-1, 0), '#');
+        sparse.set((1, 1), '#');
+        assert_eq!(to_string(&sparse, |c| *c), "#  \n  #\n");
+
+        let ansi = to_ansi(&heights(), |h| (digit(h), Rgb::grey(*h * 50)));
+        assert!(ansi.starts_with("\x1b[38;2;0;0;0m0\x1b[38;2;50;50;50m1"));
+        assert_eq!(ansi.matches("\x1b[0m\n").count(), 2);
+    }
+
+    #[test]
+    fn images() {
+        let ppm = to_ppm(&heights(), colour, 2);
+        assert!(ppm.starts_with(b"P6\n6 4\n255\n"));
+        assert_eq!(ppm.len(), 11 + 6 * 4 * 3);
+        assert_eq!(ppm[ppm.len() - 3..], [255, 255, 255]);
+
+        let png = to_png(&heights(), colour, 2).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+        assert_eq!((info.width, info.height), (6, 4));
+        assert_eq!(decoded[..info.buffer_size()], ppm[11..]);
+
+        let svg = to_svg(&heights(), colour);
+        assert!(svg.contains("viewBox=\"0 0 3 2\""));
+        assert_eq!(svg.matches("<rect").count(), 6);
+        assert!(svg.contains("<rect x=\"2\" y=\"1\" width=\"1\" height=\"1\" fill=\"#ffffff\"/>"));
+    }
+}
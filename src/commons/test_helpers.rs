@@ -1,17 +1,194 @@
+use super::grid::{parse_text, Grid};
 use super::io::{load_file_lines, ParseLinesError};
+use crate::error::AocError;
+use crate::params::{self, Params};
+use crate::solution::Solution;
+use crate::verify::{load_answers, sidecar_path};
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::panic::{self, AssertUnwindSafe};
 use std::{error::Error, fs, path::PathBuf, str::FromStr};
 
-pub struct TestCase<P1, P2> {
+/// Emits a `#[test]` for every input of a day that has an answers file next to it, named
+/// after the input, e.g. `input` for `inputs/NN` and `sample` for `inputs/extra/NN.sample`.
+/// The cases are found by `build.rs`. Cases listed under `slow_part2` only check part 1,
+/// leaving part 2 to an `#[ignore]`d test of its own.
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod tests {
+///     use super::*;
+///
+///     crate::solution_tests!(Day04, "04");
+///     crate::solution_tests!(Day15, "15", slow_part2: ["input"]);
+/// }
+/// ```
+#[macro_export]
+macro_rules! solution_tests {
+    ($solver:ty, $day:literal) => {
+        $crate::solution_tests!($solver, $day, slow_part2: []);
+    };
+    ($solver:ty, $day:literal, slow_part2: [$($case:literal),*]) => {
+        type Solver = $solver;
+        const SLOW_PART2: &[&str] = &[$($case),*];
+        include!(concat!(env!("OUT_DIR"), "/cases/day", $day, ".rs"));
+    };
+}
+
+/// What running one part of a test case should produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expect<T> {
+    Answer(T),
+    /// The part returns an error
+    Error,
+    /// The part panics, for known bugs and inputs a solver can't handle yet
+    Panic,
+    /// The part isn't run
+    Skip,
+}
+
+/// Decides whether an answer is close enough to the expected one, both in displayed form
+pub type Tolerance = fn(actual: &str, expected: &str) -> bool;
+
+fn exact(actual: &str, expected: &str) -> bool {
+    actual == expected
+}
+
+pub struct TestCase<P1, P2, X = ()> {
     pub input_path: &'static str,
-    pub part1_expected: P1,
-    pub part2_expected: P2,
+    pub part1_expected: Expect<P1>,
+    pub part2_expected: Expect<P2>,
+    /// The parser should reject the input, so neither part is run
+    pub parse_fails: bool,
+    /// Values the case passes to the solver, such as a row that differs between the sample
+    /// and the real input
+    pub params: X,
+    pub tolerance: Tolerance,
+}
+
+fn repo_path(path: &str) -> PathBuf {
+    let mut full = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    full.push(path);
+    full
 }
 
 impl<P1, P2> TestCase<P1, P2> {
+    /// A case that checks nothing yet, to build on with `part1`, `part2` and friends
+    pub fn new(input_path: &'static str) -> Self {
+        TestCase {
+            input_path,
+            part1_expected: Expect::Skip,
+            part2_expected: Expect::Skip,
+            parse_fails: false,
+            params: (),
+            tolerance: exact,
+        }
+    }
+}
+
+impl TestCase<String, String> {
+    /// A case whose expected answers are the lines of `answers_path`. A blank or missing
+    /// line leaves that part unchecked.
+    pub fn from_answers(input_path: &'static str, answers_path: &str) -> Self {
+        let mut answers = load_answers(repo_path(answers_path)).unwrap().into_iter();
+        let mut next = || match answers.next().flatten() {
+            Some(answer) => Expect::Answer(answer),
+            None => Expect::Skip,
+        };
+        TestCase {
+            part1_expected: next(),
+            part2_expected: next(),
+            ..TestCase::new(input_path)
+        }
+    }
+
+    /// A case for an extra input, with answers in the `.answer` file beside it
+    pub fn from_sidecar(input_path: &'static str) -> Self {
+        let answers_path = sidecar_path(input_path);
+        TestCase::from_answers(input_path, answers_path.to_str().unwrap())
+    }
+}
+
+impl<P1, P2, X> TestCase<P1, P2, X> {
+    pub fn part1(self, expected: P1) -> Self {
+        TestCase {
+            part1_expected: Expect::Answer(expected),
+            ..self
+        }
+    }
+
+    pub fn part2(self, expected: P2) -> Self {
+        TestCase {
+            part2_expected: Expect::Answer(expected),
+            ..self
+        }
+    }
+
+    pub fn part1_fails(self) -> Self {
+        TestCase {
+            part1_expected: Expect::Error,
+            ..self
+        }
+    }
+
+    pub fn part2_fails(self) -> Self {
+        TestCase {
+            part2_expected: Expect::Error,
+            ..self
+        }
+    }
+
+    pub fn part1_panics(self) -> Self {
+        TestCase {
+            part1_expected: Expect::Panic,
+            ..self
+        }
+    }
+
+    pub fn part2_panics(self) -> Self {
+        TestCase {
+            part2_expected: Expect::Panic,
+            ..self
+        }
+    }
+
+    /// Leaves part 2 unchecked when `slow` is set, for answers that take too long to find
+    /// in a debug build
+    pub fn slow_part2(self, slow: bool) -> Self {
+        if slow {
+            TestCase {
+                part2_expected: Expect::Skip,
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+
+    pub fn parse_fails(self) -> Self {
+        TestCase {
+            parse_fails: true,
+            ..self
+        }
+    }
+
+    pub fn tolerance(self, tolerance: Tolerance) -> Self {
+        TestCase { tolerance, ..self }
+    }
+
+    pub fn params<Y>(self, params: Y) -> TestCase<P1, P2, Y> {
+        TestCase {
+            input_path: self.input_path,
+            part1_expected: self.part1_expected,
+            part2_expected: self.part2_expected,
+            parse_fails: self.parse_fails,
+            params,
+            tolerance: self.tolerance,
+        }
+    }
+
     fn path(&self) -> PathBuf {
-        let mut input_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-        input_path.push(self.input_path);
-        input_path
+        repo_path(self.input_path)
     }
 
     pub fn load_file(&self) -> String {
@@ -19,14 +196,174 @@ impl<P1, P2> TestCase<P1, P2> {
         fs::read_to_string(input_path).unwrap()
     }
 
+    /// Reads a grid drawn in the file beside the input with the given extension, such as a
+    /// golden `09.sample.trail` to check an intermediate state against with `assert_grid_eq!`
+    pub fn load_grid<G: Grid>(&self, extension: &str, cell: impl FnMut(char) -> G::Value) -> G {
+        let path = repo_path(&format!("{}{}", self.input_path, extension));
+        let text = fs::read_to_string(path).unwrap();
+        let mut cell = cell;
+        parse_text(&text, &[], |c| Ok::<_, Infallible>(cell(c)))
+            .unwrap()
+            .grid
+    }
+
     pub fn load_file_lines<T>(
         &self,
-    ) -> impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>
+    ) -> Result<impl Iterator<Item = Result<T, ParseLinesError<<T as FromStr>::Err>>>, AocError>
     where
         T: FromStr,
-        <T as FromStr>::Err: Error,
+        <T as FromStr>::Err: Error + 'static,
     {
         let input_path = self.path();
         load_file_lines::<T>(input_path.to_str().unwrap())
     }
+
+    fn check_part<A: Display, P: Display>(
+        &self,
+        part: u8,
+        expected: &Expect<P>,
+        run: impl FnOnce() -> Result<A, AocError>,
+    ) {
+        match expected {
+            Expect::Skip => {}
+            Expect::Error => {
+                if let Ok(answer) = run() {
+                    panic!(
+                        "part {} of {}: expected an error, got {}",
+                        part, self.input_path, answer
+                    );
+                }
+            }
+            Expect::Panic => {
+                if let Ok(result) = panic::catch_unwind(AssertUnwindSafe(run)) {
+                    let outcome = match result {
+                        Ok(answer) => answer.to_string(),
+                        Err(e) => format!("error {}", e),
+                    };
+                    panic!(
+                        "part {} of {}: expected a panic, got {}",
+                        part, self.input_path, outcome
+                    );
+                }
+            }
+            Expect::Answer(expected) => {
+                let (actual, expected) = (run().unwrap().to_string(), expected.to_string());
+                assert!(
+                    (self.tolerance)(&actual, &expected),
+                    "part {} of {}: got {}, expected {}",
+                    part,
+                    self.input_path,
+                    actual,
+                    expected
+                );
+            }
+        }
+    }
+}
+
+impl<P1: Display, P2: Display, X> TestCase<P1, P2, X> {
+    /// Runs the given parser and parts, which get the case's params, comparing answers by
+    /// their displayed form
+    pub fn check_with<I, A1, A2>(
+        &self,
+        parse: impl FnOnce(&str) -> Result<I, AocError>,
+        part1: impl FnOnce(&I, &X) -> Result<A1, AocError>,
+        part2: impl FnOnce(&I, &X) -> Result<A2, AocError>,
+    ) where
+        A1: Display,
+        A2: Display,
+    {
+        let parsed = parse(&self.load_file());
+        if self.parse_fails {
+            assert!(
+                parsed.is_err(),
+                "{}: expected the parser to fail",
+                self.input_path
+            );
+            return;
+        }
+
+        let input = parsed.unwrap();
+        self.check_part(1, &self.part1_expected, || part1(&input, &self.params));
+        self.check_part(2, &self.part2_expected, || part2(&input, &self.params));
+    }
+
+    /// Runs `S` with its default params, overridden by the input's `.params` file if it has one
+    pub fn check<S: Solution>(&self) {
+        let overrides = params::load_sidecar(self.path()).unwrap();
+        let params = S::Params::with_overrides(&overrides).unwrap();
+        self.check_with(
+            S::parse,
+            |i, _| S::part1(i, &params),
+            |i, _| S::part2(i, &params),
+        );
+    }
+
+    /// Runs `S` with the case's own params
+    pub fn check_params<S: Solution<Params = X>>(&self) {
+        self.check_with(S::parse, S::part1, S::part2);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day01::Day01;
+
+    #[test]
+    fn case_options() {
+        TestCase::new("inputs/extra/01.sample")
+            .part1(24000)
+            .part2(45000)
+            .check::<Day01>();
+        TestCase::<u32, u32>::new("inputs/extra/01.sample")
+            .part2(45000)
+            .check::<Day01>();
+        TestCase::new("inputs/extra/01.sample")
+            .part1(23999)
+            .part2(45000)
+            .tolerance(|a, e| a.parse::<i64>().unwrap().abs_diff(e.parse().unwrap()) <= 1)
+            .check::<Day01>();
+        TestCase::<u32, u32>::new("inputs/extra/05.sample")
+            .parse_fails()
+            .check::<Day01>();
+        // Sum of the top n elves, with n varying per case
+        TestCase::new("inputs/extra/01.sample")
+            .params((1, 3))
+            .part1(24000)
+            .part2(45000)
+            .check_with(
+                Day01::parse,
+                |sums, &(n, _)| Ok(sums.iter().take(n).sum::<u32>()),
+                |sums, &(_, n)| Ok(sums.iter().take(n).sum::<u32>()),
+            );
+    }
+
+    #[test]
+    fn panicking_parts() {
+        TestCase::<u32, u32>::new("inputs/extra/01.sample")
+            .part1_panics()
+            .part2(45000)
+            .check_with(
+                Day01::parse,
+                |sums, _| Ok::<u32, AocError>(sums[sums.len()]),
+                |sums, _| Ok(sums.iter().take(3).sum::<u32>()),
+            );
+    }
+
+    #[test]
+    #[should_panic(expected = "expected a panic, got 24000")]
+    fn missing_panic() {
+        TestCase::<u32, u32>::new("inputs/extra/01.sample")
+            .part1_panics()
+            .check::<Day01>();
+    }
+
+    #[test]
+    #[should_panic(expected = "expected an error")]
+    fn unexpected_success() {
+        TestCase::<u32, u32>::new("inputs/extra/01.sample")
+            .part1_fails()
+            .check::<Day01>();
+    }
 }
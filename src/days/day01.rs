@@ -0,0 +1,41 @@
+use std::collections::BinaryHeap;
+
+use crate::commons::io::parse_records;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = Vec<u32>;
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, AocError> {
+        let sums: BinaryHeap<u32> =
+            parse_records::<u32, _>(input.lines().map(|l| Ok(l.to_string())), "".to_string())
+                .map(|res| res.map(|record| record.iter().sum()))
+                .collect::<Result<_, _>>()?;
+
+        // Largest first
+        Ok(sums.into_sorted_vec().into_iter().rev().collect())
+    }
+
+    fn part1(sums: &Vec<u32>, _params: &()) -> Result<u32, AocError> {
+        sums.first()
+            .copied()
+            .ok_or_else(|| AocError::no_solution("No elves"))
+    }
+
+    fn part2(sums: &Vec<u32>, _params: &()) -> Result<u32, AocError> {
+        Ok(sums.iter().take(3).sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day01, "01");
+}
@@ -1,7 +1,9 @@
 use std::str::FromStr;
 use thiserror::Error;
 
-use aoc2022::commons::io::load_argv_lines;
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
 
 #[derive(Debug, PartialEq, Eq)]
 enum Outcome {
@@ -72,7 +74,7 @@ impl Play {
 }
 
 #[derive(Debug)]
-struct Round {
+pub struct Round {
     opponent: Play,
     mine: Play,
     intended_outcome: Outcome,
@@ -92,7 +94,7 @@ impl Round {
 }
 
 #[derive(Debug, Error)]
-enum ParseError {
+pub enum ParseError {
     #[error("Unknown symbol")]
     UnknownSymbol,
 
@@ -100,6 +102,12 @@ enum ParseError {
     ParseError,
 }
 
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        AocError::parse(1, 1, e)
+    }
+}
+
 impl FromStr for Round {
     type Err = ParseError;
 
@@ -143,49 +151,30 @@ impl FromStr for Round {
     }
 }
 
-fn part1(input: &[Round]) -> u32 {
-    input.iter().map(|r| r.score()).sum()
-}
+pub struct Day02;
 
-fn part2(input: &[Round]) -> u32 {
-    input.iter().map(|r| r.intended_score()).sum()
-}
+impl Solution for Day02 {
+    type Input = Vec<Round>;
+    type Params = ();
+    type Part1 = u32;
+    type Part2 = u32;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input: Vec<Round> = load_argv_lines().collect::<Result<_, _>>()?;
+    fn parse(input: &str) -> Result<Vec<Round>, AocError> {
+        parse_str_lines(input)
+    }
 
-    println!("{:?}", part1(&input));
-    println!("{:?}", part2(&input));
+    fn part1(input: &Vec<Round>, _params: &()) -> Result<u32, AocError> {
+        Ok(input.iter().map(|r| r.score()).sum())
+    }
 
-    Ok(())
+    fn part2(input: &Vec<Round>, _params: &()) -> Result<u32, AocError> {
+        Ok(input.iter().map(|r| r.intended_score()).sum())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc2022::commons::test_helpers::TestCase;
-
-    #[test]
-    fn test_solution() {
-        let cases = [
-            TestCase {
-                input_path: "inputs/extra/02.sample",
-                part1_expected: 15,
-                part2_expected: 12,
-            },
-            TestCase {
-                input_path: "inputs/02",
-                part1_expected: 13565,
-                part2_expected: 12424,
-            },
-        ];
 
-        for case in cases {
-            let s = case.load_file();
-            let input: Vec<Round> = s.lines().map(|l| l.parse().unwrap()).collect();
-
-            assert_eq!(part1(&input), case.part1_expected);
-            assert_eq!(part2(&input), case.part2_expected);
-        }
-    }
+    crate::solution_tests!(Day02, "02");
 }
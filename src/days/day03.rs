@@ -0,0 +1,102 @@
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+use bitvec::prelude::*;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error, Hash, Eq, PartialEq)]
+pub enum ParseError {
+    #[error("Bad item")]
+    BadItem,
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        AocError::parse(1, 1, e)
+    }
+}
+
+fn priority_for_char(ch: char) -> Result<usize, ParseError> {
+    let c = ch as usize;
+    match ch {
+        'A'..='Z' => Ok(c - 64 + 26),
+        'a'..='z' => Ok(c - 96),
+        _ => Err(ParseError::BadItem),
+    }
+}
+
+#[derive(Debug)]
+pub struct Bag {
+    c1: BitArray<[u64; 1]>,
+    c2: BitArray<[u64; 1]>,
+    all: BitArray<[u64; 1]>,
+}
+
+impl Bag {
+    pub fn badge(&self) -> Option<usize> {
+        let inter = self.c1 & self.c2;
+        inter.first_one()
+    }
+}
+
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s1, s2) = s.split_at(s.len() / 2);
+        let mut c1 = bitarr![u64, Lsb0; 0; 64];
+        let mut c2 = bitarr![u64, Lsb0; 0; 64];
+        let mut all = bitarr![u64, Lsb0; 0; 64];
+        for c in s1.chars() {
+            let p = priority_for_char(c)?;
+            c1.set(p, true);
+            all.set(p, true);
+        }
+        for c in s2.chars() {
+            let p = priority_for_char(c)?;
+            c2.set(p, true);
+            all.set(p, true);
+        }
+        Ok(Bag { c1, c2, all })
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Bag>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Bag>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(input: &Vec<Bag>, _params: &()) -> Result<usize, AocError> {
+        input
+            .iter()
+            .map(|b| b.badge().ok_or_else(|| AocError::no_solution("No matches")))
+            .sum::<Result<_, _>>()
+    }
+
+    fn part2(input: &Vec<Bag>, _params: &()) -> Result<usize, AocError> {
+        input
+            .chunks_exact(3)
+            .map(|window| {
+                let inter = window[0].all & window[1].all & window[2].all;
+                inter
+                    .first_one()
+                    .ok_or_else(|| AocError::no_solution("No matches"))
+            })
+            .sum::<Result<_, _>>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day03, "03");
+}
@@ -0,0 +1,107 @@
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+use peg::str::LineCol;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+peg::parser! {
+    grammar assignment_parser() for str {
+        rule number() -> u8
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule range() -> RangeInclusive<u8>
+            = start:number() "-" end:number() {
+                start..=end
+            }
+
+        pub rule assignment() -> Assignment
+            = first:range() "," second:range() {
+                Assignment { first, second }
+            }
+    }
+
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Assignment {
+    first: RangeInclusive<u8>,
+    second: RangeInclusive<u8>,
+}
+
+impl Assignment {
+    pub fn fully_contains(&self) -> bool {
+        (self.first.contains(self.second.start()) && self.first.contains(self.second.end()))
+            || (self.second.contains(self.first.start()) && self.second.contains(self.first.end()))
+    }
+
+    pub fn any_overlap(&self) -> bool {
+        (self.first.contains(self.second.start()) || self.first.contains(self.second.end()))
+            || (self.second.contains(self.first.start()) || self.second.contains(self.first.end()))
+    }
+}
+
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{},{}-{}",
+            self.first.start(),
+            self.first.end(),
+            self.second.start(),
+            self.second.end()
+        )
+    }
+}
+
+impl FromStr for Assignment {
+    type Err = peg::error::ParseError<LineCol>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        assignment_parser::assignment(s)
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Assignment>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Assignment>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(input: &Vec<Assignment>, _params: &()) -> Result<usize, AocError> {
+        Ok(input.iter().filter(|a| a.fully_contains()).count())
+    }
+
+    fn part2(input: &Vec<Assignment>, _params: &()) -> Result<usize, AocError> {
+        Ok(input.iter().filter(|a| a.any_overlap()).count())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day04, "04");
+
+    proptest! {
+        #[test]
+        fn round_trip(a: u8, b: u8, c: u8, d: u8) {
+            let assignment = Assignment { first: a..=b, second: c..=d };
+            prop_assert_eq!(assignment.to_string().parse::<Assignment>().unwrap(), assignment);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "[0-9,-]{0,12}") {
+            let _ = Day04::parse(&s);
+        }
+    }
+}
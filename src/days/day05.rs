@@ -0,0 +1,271 @@
+use crate::commons::io::{parse_str_lines, parse_two_sections_with};
+use crate::error::AocError;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::fmt;
+use std::str::FromStr;
+
+peg::parser! {
+    grammar shipment_parser() for str {
+        rule number() -> usize
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule present_shipping_crate() -> Option<char>
+            = "[" c:$(['A'..='Z']) "]" { c.chars().next() }
+        rule missing_shipping_crate() -> Option<char>
+            = "   " { None }
+
+        rule shipping_crate() -> Option<char>
+            = present_shipping_crate() / missing_shipping_crate()
+
+        rule shipping_crates_entry() -> Option<char>
+            = s:shipping_crate() " "  {
+                s
+            }
+
+        pub rule shipping_crates() -> Vec<Option<char>>
+            = l:shipping_crates_entry()* e:shipping_crate() {
+                let mut v = l;
+                v.push(e);
+                v
+            }
+
+        // Stacks are numbered from 1 in the input
+        rule stack() -> usize
+            = n:number() {? n.checked_sub(1).ok_or("stack number") }
+
+        pub rule move_instruction() -> MoveInstruction
+            = "move " count:number() " from " from:stack() " to " to:stack() {
+                MoveInstruction { count, from, to }
+            }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveInstruction {
+    count: usize,
+    from: usize,
+    to: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleInput {
+    stacks: Vec<Vec<char>>,
+    instructions: Vec<MoveInstruction>,
+}
+
+fn parse_stacks(drawing: &str) -> Result<Vec<Vec<char>>, AocError> {
+    // The last line of the drawing only numbers the stacks
+    let lines = drawing.lines().collect::<Vec<_>>();
+    let (labels, rows) = lines
+        .split_last()
+        .ok_or_else(|| AocError::parse(1, 1, "missing crate stacks"))?;
+    let stack_count = (labels.len() + 1) / 4;
+    let mut stacks: Vec<Vec<char>> = (0..stack_count)
+        .map(|_| Vec::with_capacity(rows.len()))
+        .collect();
+    for (line, l) in rows.iter().enumerate() {
+        let crates =
+            shipment_parser::shipping_crates(l).map_err(|e| AocError::from(e).on_line(line + 1))?;
+        for (i, c_opt) in crates.into_iter().enumerate() {
+            if let Some(c) = c_opt {
+                let stack = stacks
+                    .get_mut(i)
+                    .ok_or_else(|| AocError::parse(line + 1, i * 4 + 1, "crate outside stacks"))?;
+                stack.push(c);
+            }
+        }
+    }
+
+    for s in &mut stacks {
+        s.reverse();
+    }
+    Ok(stacks)
+}
+
+impl FromStr for MoveInstruction {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(shipment_parser::move_instruction(s)?)
+    }
+}
+
+impl fmt::Display for MoveInstruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.count,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
+/// Draws the stacks the way the puzzle does, followed by the instructions
+impl fmt::Display for PuzzleInput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        for row in (0..height).rev() {
+            let crates = self.stacks.iter().map(|s| match s.get(row) {
+                Some(c) => format!("[{}]", c),
+                None => "   ".to_string(),
+            });
+            writeln!(f, "{}", crates.format(" "))?;
+        }
+        let labels = (1..=self.stacks.len()).map(|i| format!(" {} ", i));
+        writeln!(f, "{}", labels.format(" "))?;
+        writeln!(f)?;
+        for instruction in &self.instructions {
+            writeln!(f, "{}", instruction)?;
+        }
+        Ok(())
+    }
+}
+
+fn answer(stacks: &[Vec<char>]) -> Result<String, AocError> {
+    stacks
+        .iter()
+        .enumerate()
+        .map(|(i, stack)| {
+            stack
+                .last()
+                .ok_or_else(|| AocError::no_solution(format!("stack {} ends up empty", i + 1)))
+        })
+        .collect()
+}
+
+/// Takes the top `instruction.count` crates off the stack they're moved from, top last
+fn take(stacks: &mut [Vec<char>], instruction: &MoveInstruction) -> Result<Vec<char>, AocError> {
+    let src = &mut stacks[instruction.from];
+    let at = src.len().checked_sub(instruction.count).ok_or_else(|| {
+        AocError::no_solution(format!(
+            "can't {}, stack {} only holds {}",
+            instruction,
+            instruction.from + 1,
+            src.len()
+        ))
+    })?;
+    Ok(src.split_off(at))
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = PuzzleInput;
+    type Params = ();
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<PuzzleInput, AocError> {
+        let (stacks, instructions): (_, Vec<MoveInstruction>) =
+            parse_two_sections_with(input, parse_stacks, parse_str_lines)?;
+        // Instructions start after the drawing and the blank line below it
+        let first_line = input.lines().take_while(|l| !l.trim().is_empty()).count() + 2;
+        for (i, instruction) in instructions.iter().enumerate() {
+            if let Some(stack) = [instruction.from, instruction.to]
+                .into_iter()
+                .find(|s| *s >= stacks.len())
+            {
+                return Err(AocError::parse(
+                    first_line + i,
+                    1,
+                    format!("no stack {}, there are {}", stack + 1, stacks.len()),
+                ));
+            }
+        }
+        Ok(PuzzleInput {
+            stacks,
+            instructions,
+        })
+    }
+
+    fn part1(input: &PuzzleInput, _params: &()) -> Result<String, AocError> {
+        let mut stacks = input.stacks.clone();
+        for instruction in &input.instructions {
+            // One crate at a time, so the moved crates land in reverse order
+            let moved = take(&mut stacks, instruction)?;
+            stacks[instruction.to].extend(moved.into_iter().rev());
+        }
+
+        answer(&stacks)
+    }
+
+    fn part2(input: &PuzzleInput, _params: &()) -> Result<String, AocError> {
+        let mut stacks = input.stacks.clone();
+        for instruction in &input.instructions {
+            let moved = take(&mut stacks, instruction)?;
+            stacks[instruction.to].extend(moved);
+        }
+
+        answer(&stacks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day05, "05");
+
+    fn puzzle_input() -> impl Strategy<Value = PuzzleInput> {
+        // Labels are only one digit wide up to 9 stacks
+        (1..=9usize).prop_flat_map(|n| {
+            let stacks = vec(vec(proptest::char::range('A', 'Z'), 0..6), n);
+            let instruction = (0..100usize, 0..n, 0..n)
+                .prop_map(|(count, from, to)| MoveInstruction { count, from, to });
+            (stacks, vec(instruction, 1..10)).prop_map(|(stacks, instructions)| PuzzleInput {
+                stacks,
+                instructions,
+            })
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(input in puzzle_input()) {
+            prop_assert_eq!(Day05::parse(&input.to_string()).unwrap(), input);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "(\\[[A-Z]\\]| |[0-9]|\n|move |from |to ){0,30}") {
+            let _ = Day05::parse(&s);
+        }
+    }
+
+    #[test]
+    fn stacks_numbered_from_one() {
+        assert!("move 1 from 0 to 1".parse::<MoveInstruction>().is_err());
+    }
+
+    #[test]
+    fn impossible_moves() {
+        let drawing = "[A]    \n[B] [C]\n 1   2 \n\n";
+        let err = Day05::parse(&format!(
+            "{}move 1 from 1 to 2\nmove 1 from 3 to 1\n",
+            drawing
+        ))
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 6, column 1: no stack 3, there are 2"
+        );
+
+        let input = Day05::parse(&format!("{}move 2 from 2 to 1\n", drawing)).unwrap();
+        assert_eq!(
+            Day05::part1(&input, &()).unwrap_err().to_string(),
+            "No solution found: can't move 2 from 2 to 1, stack 2 only holds 1"
+        );
+        assert!(Day05::part2(&input, &()).is_err());
+
+        let input = Day05::parse(&format!("{}move 1 from 2 to 1\n", drawing)).unwrap();
+        assert_eq!(
+            Day05::part1(&input, &()).unwrap_err().to_string(),
+            "No solution found: stack 2 ends up empty"
+        );
+    }
+}
@@ -1,7 +1,7 @@
-use aoc2022::commons::io::load_argv_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
 use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::error::Error;
 use std::hash::Hash;
 
 #[derive(Debug)]
@@ -49,61 +49,45 @@ impl<T: Eq + Hash + Copy> Window<T> {
     }
 }
 
-fn solve(input: &str, len: usize) -> usize {
+fn solve(input: &str, len: usize) -> Result<usize, AocError> {
     let mut window = Window::new(len);
 
-    for (i, c) in input.chars().into_iter().enumerate() {
+    for (i, c) in input.chars().enumerate() {
         window.push(c);
         if window.unique_count() == len {
-            return i + 1;
+            return Ok(i + 1);
         }
     }
-    panic!("NOT HERE");
+    Err(AocError::no_solution(format!(
+        "no run of {} distinct characters",
+        len
+    )))
 }
 
-fn part1(input: &str) -> usize {
-    solve(input, 4)
-}
+pub struct Day06;
 
-fn part2(input: &str) -> usize {
-    solve(input, 14)
-}
+impl Solution for Day06 {
+    type Input = String;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let lines = load_argv_lines().collect::<Result<Vec<String>, _>>()?;
-    let input = &lines[0];
+    fn parse(input: &str) -> Result<String, AocError> {
+        Ok(input.lines().next().unwrap_or_default().to_string())
+    }
 
-    println!("{}", part1(input));
-    println!("{}", part2(input));
+    fn part1(input: &String, _params: &()) -> Result<usize, AocError> {
+        solve(input, 4)
+    }
 
-    Ok(())
+    fn part2(input: &String, _params: &()) -> Result<usize, AocError> {
+        solve(input, 14)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aoc2022::commons::test_helpers::TestCase;
-
-    #[test]
-    fn test_solution() {
-        let cases = [
-            TestCase {
-                input_path: "inputs/extra/06.sample",
-                part1_expected: 7,
-                part2_expected: 19,
-            },
-            TestCase {
-                input_path: "inputs/06",
-                part1_expected: 1760,
-                part2_expected: 2974,
-            },
-        ];
 
-        for case in cases {
-            let s = case.load_file();
-            let input = s.lines().next().unwrap().to_string();
-            assert_eq!(part1(&input), case.part1_expected);
-            assert_eq!(part2(&input), case.part2_expected);
-        }
-    }
+    crate::solution_tests!(Day06, "06");
 }
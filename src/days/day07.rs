@@ -0,0 +1,206 @@
+use crate::commons::io::parse_input_with;
+use crate::error::AocError;
+use crate::params;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LsEntry {
+    Directory(String),
+    File(usize, String),
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    Ls(Vec<LsEntry>),
+    Cd(String),
+}
+
+impl fmt::Display for LsEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LsEntry::Directory(name) => write!(f, "dir {}", name),
+            LsEntry::File(size, name) => write!(f, "{} {}", size, name),
+        }
+    }
+}
+
+/// A command as it appears in the terminal output, ending with a newline
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Ls(entries) => {
+                writeln!(f, "$ ls")?;
+                for entry in entries {
+                    writeln!(f, "{}", entry)?;
+                }
+                Ok(())
+            }
+            Command::Cd(path) => writeln!(f, "$ cd {}", path),
+        }
+    }
+}
+
+peg::parser! {
+    grammar shell_parser() for str {
+        rule number() -> usize
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule path() -> String
+            = n:$(['!'..='~']+) { n.to_string() }
+
+        rule dir_entry() -> LsEntry
+            = "dir " p:path() { LsEntry::Directory(p.to_string()) }
+
+        rule file_entry() -> LsEntry
+            = size:number() " " p:path() { LsEntry::File(size, p.to_string()) }
+
+        rule ls_entry() -> LsEntry
+            = (dir_entry() / file_entry())
+
+        rule ls_line() -> LsEntry
+            = entry:ls_entry() "\n" { entry }
+
+        rule ls_lines() -> Vec<LsEntry>
+            = ls_line()+
+
+        rule ls() -> Command
+            = "$ ls\n" lines:ls_lines() {
+                Command::Ls(lines)
+        }
+
+        rule cd() -> Command
+            = "$ cd " p:path() "\n" {
+                Command::Cd(p.to_string())
+        }
+
+        rule cmd_entry() -> Command
+            = ls() / cd()
+
+        pub rule cmds() -> Vec<Command>
+            = cmd_entry()+
+    }
+}
+
+fn dir_sizes(input: &str) -> Result<Vec<usize>, AocError> {
+    let cmds = shell_parser::cmds(input)?;
+
+    let mut sizes = HashMap::new();
+    let root = PathBuf::new();
+    let mut current = root.clone();
+
+    for cmd in cmds {
+        match cmd {
+            Command::Cd(path) => {
+                current = match path.as_str() {
+                    "/" => root.to_path_buf(),
+                    // Like a shell, going up from the root stays there
+                    ".." => current.parent().unwrap_or(&root).to_path_buf(),
+                    _a => current.join(path),
+                };
+            }
+            Command::Ls(files) => {
+                for file in files {
+                    if let LsEntry::File(size, _) = file {
+                        let c = current.clone();
+                        for ancestor in c.ancestors() {
+                            let entry = sizes.entry(ancestor.to_path_buf());
+                            let ancestor_size = entry.or_insert(0);
+                            *ancestor_size += size;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut dir_sizes = sizes.values().copied().collect::<Vec<_>>();
+    dir_sizes.sort();
+    Ok(dir_sizes)
+}
+
+params! {
+    pub struct Day07Params {
+        disk_size: usize = 70_000_000,
+        /// Free space the update needs
+        needed: usize = 30_000_000,
+    }
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Vec<usize>;
+    type Params = Day07Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, AocError> {
+        Ok(parse_input_with(input, dir_sizes)?)
+    }
+
+    fn part1(input: &Vec<usize>, _params: &Day07Params) -> Result<usize, AocError> {
+        let mut sum = 0;
+        for &size in input {
+            if size > 100000 {
+                continue;
+            }
+            sum += size;
+        }
+
+        Ok(sum)
+    }
+
+    fn part2(input: &Vec<usize>, params: &Day07Params) -> Result<usize, AocError> {
+        let used = input
+            .last()
+            .ok_or_else(|| AocError::no_solution("no directories"))?;
+        let available = params
+            .disk_size
+            .checked_sub(*used)
+            .ok_or_else(|| AocError::no_solution("files don't fit on the disk"))?;
+        for &size in input {
+            if (available + size) > params.needed {
+                return Ok(size);
+            }
+        }
+        Err(AocError::no_solution("no directory frees enough space"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day07, "07");
+
+    fn command() -> impl Strategy<Value = Command> {
+        let name = "[!-~]{1,8}";
+        let entry = prop_oneof![
+            name.prop_map(LsEntry::Directory),
+            (any::<usize>(), name).prop_map(|(size, name)| LsEntry::File(size, name)),
+        ];
+        prop_oneof![
+            vec(entry, 1..5).prop_map(Command::Ls),
+            name.prop_map(Command::Cd),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(cmds in vec(command(), 1..10)) {
+            let printed = cmds.iter().map(Command::to_string).collect::<String>();
+            prop_assert_eq!(shell_parser::cmds(&printed).unwrap(), cmds);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "(\\$ ls\n|\\$ cd |dir |[0-9]{1,3} |\\.\\.|/|a|\n){0,30}") {
+            let _ = Day07::parse(&s);
+        }
+    }
+}
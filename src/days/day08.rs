@@ -0,0 +1,95 @@
+use crate::commons::grid::{parse_text, BitGrid, DenseGrid, Grid, SingleVecGrid};
+use crate::error::AocError;
+use crate::solution::Solution;
+use lazy_static::lazy_static;
+
+lazy_static! {
+    static ref DIRS: Vec<(isize, isize)> = vec![(0, -1), (-1, 0), (0, 1), (1, 0)];
+}
+
+fn visible_count(input: &SingleVecGrid<u8>) -> usize {
+    let mut visible = BitGrid::new(input.width(), input.height());
+
+    // Looking in from the left of each turned view covers all four sides
+    let trees = input.view();
+    for side in [
+        trees,
+        trees.rotate_clockwise(),
+        trees.rotate_half(),
+        trees.rotate_anticlockwise(),
+    ] {
+        for y in 0..side.height() {
+            let mut max = None;
+            for x in 0..side.width() {
+                let tree_height = side.at(&(x, y));
+                if tree_height > max {
+                    visible.set(side.source(&(x, y)), true);
+                    max = tree_height;
+                }
+            }
+        }
+    }
+
+    visible.set_cell_count()
+}
+
+fn treehouse_score(grid: &SingleVecGrid<u8>, x: usize, y: usize) -> usize {
+    let tree_height = grid.at(&(x, y)).expect("Bad coord");
+    DIRS.iter()
+        .map(|step| {
+            let mut score = 0;
+            for tree in grid.raycast((x, y), *step).skip(1) {
+                score += 1;
+                if tree >= tree_height {
+                    break;
+                }
+            }
+            score
+        })
+        .product()
+}
+
+fn best_treehouse_score(input: &SingleVecGrid<u8>) -> usize {
+    (0..input.height())
+        .map(|y| {
+            (0..input.width())
+                .map(|x| treehouse_score(input, x, y))
+                .max()
+                .unwrap_or(0)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = SingleVecGrid<u8>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<SingleVecGrid<u8>, AocError> {
+        let trees = parse_text(input, &[], |c| {
+            c.to_digit(10)
+                .map(|height| height as u8)
+                .ok_or_else(|| format!("bad tree height {:?}", c))
+        })?;
+        Ok(trees.grid)
+    }
+
+    fn part1(input: &SingleVecGrid<u8>, _params: &()) -> Result<usize, AocError> {
+        Ok(visible_count(input))
+    }
+
+    fn part2(input: &SingleVecGrid<u8>, _params: &()) -> Result<usize, AocError> {
+        Ok(best_treehouse_score(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day08, "08");
+}
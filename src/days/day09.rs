@@ -0,0 +1,135 @@
+use crate::commons::geom::Point;
+use crate::commons::grid::{DynamicGrid, Grid, MOORE};
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+use std::str::FromStr;
+
+/// The cells visited by the knot behind the head and by the last knot, with up as +y
+fn trails(input: &[Instruction]) -> (DynamicGrid<bool>, DynamicGrid<bool>) {
+    let mut elems = (0..=9)
+        .map(|_| Point::origin())
+        .collect::<Vec<Point<isize>>>();
+    let mut part1 = DynamicGrid::new();
+    let mut part2 = DynamicGrid::new();
+
+    for instruction in input {
+        for _ in 0..instruction.n() {
+            let mut last = 0;
+            elems[last] += instruction.as_dir();
+            for knot in 1..=9 {
+                let last_knot = elems[last];
+                let mut tail = elems[knot];
+                let diff = (last_knot - elems[knot]).tuple_copy();
+                // A knot no longer touching the one ahead steps towards it, diagonally if need be
+                if diff != (0, 0) && !MOORE.contains(&diff) {
+                    tail += (diff.0.signum(), diff.1.signum());
+                }
+                elems[knot] = tail;
+                last = knot;
+            }
+
+            part1.set(elems[1].tuple_copy(), true);
+            part2.set(elems[9].tuple_copy(), true);
+        }
+    }
+
+    (part1, part2)
+}
+
+fn solve(input: &[Instruction]) -> (usize, usize) {
+    let (part1, part2) = trails(input);
+    let visited = |grid: &DynamicGrid<bool>| grid.points().filter(|(_, v)| **v).count();
+    (visited(&part1), visited(&part2))
+}
+
+#[derive(Debug)]
+pub enum Instruction {
+    Up(isize),
+    Left(isize),
+    Down(isize),
+    Right(isize),
+}
+
+impl Instruction {
+    fn as_dir(&self) -> (isize, isize) {
+        match self {
+            Instruction::Up(_) => (0, 1),
+            Instruction::Left(_) => (-1, 0),
+            Instruction::Down(_) => (0, -1),
+            Instruction::Right(_) => (1, 0),
+        }
+    }
+
+    fn n(&self) -> isize {
+        match self {
+            Instruction::Up(n) => *n,
+            Instruction::Left(n) => *n,
+            Instruction::Down(n) => *n,
+            Instruction::Right(n) => *n,
+        }
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = AocError;
+
+    fn from_str(l: &str) -> Result<Self, Self::Err> {
+        let (first, second) = l
+            .split_once(' ')
+            .ok_or_else(|| AocError::parse(1, 1, "expected direction and distance"))?;
+        let second = second
+            .parse()
+            .map_err(|e| AocError::parse(1, first.len() + 2, e))?;
+        match first {
+            "U" => Ok(Instruction::Up(second)),
+            "L" => Ok(Instruction::Left(second)),
+            "D" => Ok(Instruction::Down(second)),
+            "R" => Ok(Instruction::Right(second)),
+            _ => Err(AocError::parse(
+                1,
+                1,
+                format!("unknown direction {:?}", first),
+            )),
+        }
+    }
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Vec<Instruction>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(input: &Vec<Instruction>, _params: &()) -> Result<usize, AocError> {
+        Ok(solve(input).0)
+    }
+
+    fn part2(input: &Vec<Instruction>, _params: &()) -> Result<usize, AocError> {
+        Ok(solve(input).1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::grid::SingleVecGrid;
+    use crate::commons::test_helpers::TestCase;
+
+    crate::solution_tests!(Day09, "09");
+
+    #[test]
+    fn sample_trail() {
+        let case = TestCase::<usize, usize>::new("inputs/extra/09.sample");
+        let (trail, _) = trails(&Day09::parse(&case.load_file()).unwrap());
+        // Rows go down from the lowest y, so this is the puzzle's drawing upside down
+        let golden: SingleVecGrid<bool> = case.load_grid(".trail", |c| c == '#');
+        crate::assert_grid_eq!(trail, golden);
+    }
+}
@@ -0,0 +1,146 @@
+use crate::commons::grid::{BitGrid, Grid};
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+use std::{num::ParseIntError, str::FromStr};
+use thiserror::Error;
+
+#[derive(Debug)]
+pub enum Instruction {
+    AddX(isize),
+    Noop,
+}
+
+impl Instruction {
+    pub fn cycle_count(&self) -> usize {
+        match self {
+            Self::AddX(_) => 2,
+            Self::Noop => 1,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ParseError {
+    #[error("Unknown instruction")]
+    UnknownInstruction,
+    #[error("Missing operand")]
+    MissingOperand,
+    #[error("Parse fail")]
+    IntParseFail(#[from] ParseIntError),
+}
+
+impl From<ParseError> for AocError {
+    fn from(e: ParseError) -> Self {
+        AocError::parse(1, 1, e)
+    }
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut i = s.split(' ');
+        match i.next() {
+            Some("noop") => Ok(Instruction::Noop),
+            Some("addx") => {
+                let x = i.next().ok_or(ParseError::MissingOperand)?.parse()?;
+                Ok(Instruction::AddX(x))
+            }
+            _ => Err(ParseError::UnknownInstruction),
+        }
+    }
+}
+
+fn signal_strength(input: &[Instruction]) -> Result<isize, AocError> {
+    let mut x = 1;
+    let mut x_values = Vec::new();
+
+    for i in input {
+        for _ in 0..i.cycle_count() {
+            x_values.push(x);
+        }
+        if let Instruction::AddX(add) = i {
+            x += add;
+        }
+    }
+
+    [20, 60, 100, 140, 180, 220]
+        .iter()
+        .map(|&cycle: &usize| {
+            let x = cycle
+                .checked_sub(1)
+                .and_then(|i| x_values.get(i))
+                .ok_or_else(|| {
+                    AocError::no_solution(format!("the program ends before cycle {}", cycle))
+                })?;
+            Ok(cycle as isize * x)
+        })
+        .sum()
+}
+
+fn render(input: &[Instruction]) -> Result<String, AocError> {
+    let mut x_reg = 1;
+    let mut clock = 0;
+    let mut grid = BitGrid::new(40, 6);
+
+    for ins in input.iter() {
+        for _ in 0..ins.cycle_count() {
+            let y = clock / 40;
+            let x = clock % 40;
+            let mut pixel = grid.at_mut(&(x, y)).ok_or_else(|| {
+                AocError::no_solution("the program runs past the bottom of the screen")
+            })?;
+            *pixel = (x_reg - x as isize).abs() < 2;
+            clock += 1;
+        }
+        if let Instruction::AddX(add) = ins {
+            x_reg += add;
+        }
+    }
+
+    grid.decode_string().map_err(AocError::no_solution)
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Params = ();
+    type Part1 = isize;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Instruction>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(input: &Vec<Instruction>, _params: &()) -> Result<isize, AocError> {
+        signal_strength(input)
+    }
+
+    fn part2(input: &Vec<Instruction>, _params: &()) -> Result<String, AocError> {
+        render(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day10, "10");
+
+    #[test]
+    fn programs_of_the_wrong_length() {
+        let short = Day10::parse("noop\naddx 3\naddx -5\n").unwrap();
+        assert_eq!(
+            Day10::part1(&short, &()).unwrap_err().to_string(),
+            "No solution found: the program ends before cycle 20"
+        );
+
+        let long = Day10::parse(&"noop\n".repeat(241)).unwrap();
+        assert_eq!(
+            Day10::part2(&long, &()).unwrap_err().to_string(),
+            "No solution found: the program runs past the bottom of the screen"
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use crate::commons::io::{normalise_input, sections};
+use crate::error::AocError;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::cell::RefCell;
+use std::fmt;
+
+peg::parser! {
+    grammar monkey_parser() for str {
+        rule number() -> usize
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule number_list() -> Vec<usize>
+            = nums:(number() ** ", ")  {
+                nums
+            }
+
+        rule var_num() -> Var =
+            n: number() {
+                Var::Literal(n)
+        }
+
+        rule var_old() -> Var =
+            "old" {
+                Var::Old
+        }
+
+        rule var() -> Var
+            = var_num() / var_old()
+
+        rule operation() -> Operation =
+            precedence!{
+                "+ " v:var() {Operation::Add(v)}
+                "- " v:var() {Operation::Sub(v)}
+                "* " v:var() {Operation::Mul(v)}
+                "/ " v:var() {Operation::Div(v)}
+            }
+
+        rule operation_str() -> Operation =
+            "  Operation: new = old " o:operation() { o }
+
+        rule throw_rule() -> usize =
+            "throw to monkey " n:number() { n }
+
+        rule test() -> Test =
+            "  Test: divisible by " div:number()  "\n"
+                "    If true: " t:throw_rule() "\n"
+                "    If false: " f:throw_rule() {
+                    Test {
+                        divisible_by: div,
+                        if_true: t,
+                        if_false: f,
+                    }
+                }
+
+        pub rule monkey() -> Monkey
+            = "Monkey " id:number() ":\n"
+                "  Starting items: " items:number_list() "\n"
+                op:operation_str() "\n"
+                test:test() {
+                    Monkey {
+                        id,
+                        items,
+                        op,
+                        test,
+                        inspections: 0,
+                    }
+                }
+
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Test {
+    divisible_by: usize,
+    if_true: usize,
+    if_false: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Var {
+    Literal(usize),
+    Old,
+}
+
+impl Var {
+    pub fn get(&self, old: usize) -> usize {
+        match self {
+            Self::Literal(n) => *n,
+            Self::Old => old,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Operation {
+    Add(Var),
+    Div(Var),
+    Mul(Var),
+    Sub(Var),
+}
+
+impl Operation {
+    /// The new worry level, or `None` if it doesn't fit or divides by zero
+    pub fn apply(&self, old: usize) -> Option<usize> {
+        match self {
+            Self::Add(v) => old.checked_add(v.get(old)),
+            Self::Div(v) => old.checked_div(v.get(old)),
+            Self::Mul(v) => old.checked_mul(v.get(old)),
+            Self::Sub(v) => old.checked_sub(v.get(old)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Monkey {
+    id: usize,
+    items: Vec<usize>,
+    op: Operation,
+    test: Test,
+    inspections: usize,
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Literal(n) => write!(f, "{}", n),
+            Self::Old => write!(f, "old"),
+        }
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Add(v) => write!(f, "+ {}", v),
+            Self::Div(v) => write!(f, "/ {}", v),
+            Self::Mul(v) => write!(f, "* {}", v),
+            Self::Sub(v) => write!(f, "- {}", v),
+        }
+    }
+}
+
+impl fmt::Display for Test {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  Test: divisible by {}", self.divisible_by)?;
+        writeln!(f, "    If true: throw to monkey {}", self.if_true)?;
+        write!(f, "    If false: throw to monkey {}", self.if_false)
+    }
+}
+
+/// The monkey's block of notes, without its inspection count
+impl fmt::Display for Monkey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Monkey {}:", self.id)?;
+        writeln!(f, "  Starting items: {}", self.items.iter().format(", "))?;
+        writeln!(f, "  Operation: new = old {}", self.op)?;
+        write!(f, "{}", self.test)
+    }
+}
+
+impl Monkey {
+    /// Checks the notes of the monkey at `index` only throw to other monkeys among `count`,
+    /// with errors on the line of the block at fault
+    fn validate(&self, index: usize, count: usize) -> Result<(), AocError> {
+        if self.test.divisible_by == 0 {
+            return Err(AocError::parse(4, 22, "can't test divisibility by 0"));
+        }
+        for (line, column, target) in [(5, 30, self.test.if_true), (6, 31, self.test.if_false)] {
+            if target == index {
+                return Err(AocError::parse(line, column, "monkey throws to itself"));
+            }
+            if target >= count {
+                return Err(AocError::parse(
+                    line,
+                    column,
+                    format!("no monkey {}, there are {}", target, count),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn step(
+        &mut self,
+        monkeys: &[RefCell<Monkey>],
+        worry_update_fn: impl Fn(usize) -> usize,
+    ) -> Result<(), AocError> {
+        for item in &self.items {
+            self.inspections += 1;
+            let new_worry_level = worry_update_fn(self.op.apply(*item).ok_or_else(|| {
+                AocError::no_solution(format!(
+                    "monkey {} can't work out old {} for old = {}",
+                    self.id, self.op, item
+                ))
+            })?);
+            let throw_to = if new_worry_level.is_multiple_of(self.test.divisible_by) {
+                self.test.if_true
+            } else {
+                self.test.if_false
+            };
+            monkeys[throw_to].borrow_mut().items.push(new_worry_level);
+        }
+        self.items.clear();
+        Ok(())
+    }
+}
+
+fn solve(
+    input: &[Monkey],
+    iterations: usize,
+    worry_update_fn: impl Fn(usize) -> usize,
+) -> Result<usize, AocError> {
+    if input.len() < 2 {
+        return Err(AocError::no_solution(
+            "the two most active monkeys need at least two monkeys",
+        ));
+    }
+    let mut input = input
+        .iter()
+        .map(|x| RefCell::new(x.clone()))
+        .collect::<Vec<_>>();
+
+    for _ in 0..iterations {
+        for monkey in &input {
+            monkey.borrow_mut().step(&input, &worry_update_fn)?;
+        }
+    }
+
+    input.sort_by_key(|m| m.borrow().inspections);
+
+    let last = input[input.len() - 1].borrow().inspections;
+    let second_last = input[input.len() - 2].borrow().inspections;
+
+    Ok(last * second_last)
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Monkey>, AocError> {
+        let input = normalise_input(input);
+        let blocks = sections(&input);
+        let count = blocks.len();
+        Ok(blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                block.parse_with(|text| {
+                    let monkey = monkey_parser::monkey(text)?;
+                    monkey.validate(i, count)?;
+                    Ok::<_, AocError>(monkey)
+                })
+            })
+            .collect::<Result<_, _>>()?)
+    }
+
+    fn part1(input: &Vec<Monkey>, _params: &()) -> Result<usize, AocError> {
+        solve(input, 20, |x| x / 3)
+    }
+
+    fn part2(input: &Vec<Monkey>, _params: &()) -> Result<usize, AocError> {
+        let div_multiplier = input
+            .iter()
+            .try_fold(1usize, |product, m| {
+                product.checked_mul(m.test.divisible_by)
+            })
+            .ok_or_else(|| AocError::no_solution("the divisors' product doesn't fit"))?;
+        solve(input, 10_000, |x| x % div_multiplier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day11, "11");
+
+    fn monkey() -> impl Strategy<Value = Monkey> {
+        let var = prop_oneof![any::<usize>().prop_map(Var::Literal), Just(Var::Old)];
+        let op = prop_oneof![
+            var.clone().prop_map(Operation::Add),
+            var.clone().prop_map(Operation::Div),
+            var.clone().prop_map(Operation::Mul),
+            var.prop_map(Operation::Sub),
+        ];
+        let test = (1..=usize::MAX, any::<usize>(), any::<usize>()).prop_map(
+            |(divisible_by, if_true, if_false)| Test {
+                divisible_by,
+                if_true,
+                if_false,
+            },
+        );
+        (any::<usize>(), vec(any::<usize>(), 0..5), op, test).prop_map(|(id, items, op, test)| {
+            Monkey {
+                id,
+                items,
+                op,
+                test,
+                inspections: 0,
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(mut monkeys in vec(monkey(), 2..5)) {
+            // Point every throw at some other monkey, so the notes are valid
+            let count = monkeys.len();
+            for (i, m) in monkeys.iter_mut().enumerate() {
+                m.test.if_true = (i + 1 + m.test.if_true % (count - 1)) % count;
+                m.test.if_false = (i + 1 + m.test.if_false % (count - 1)) % count;
+            }
+            let printed = monkeys.iter().map(Monkey::to_string).join("\n\n");
+            prop_assert_eq!(Day11::parse(&printed).unwrap(), monkeys);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "(Monkey |[0-9]{1,3}|:|, | |\n|old|[-+*/])*") {
+            let _ = Day11::parse(&s);
+        }
+    }
+
+    fn notes(divisible_by: usize, if_true: usize, if_false: usize) -> String {
+        format!(
+            "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 19\n\
+             {}\n\nMonkey 1:\n  Starting items: 54\n  Operation: new = old + 6\n\
+             {}\n",
+            Test {
+                divisible_by,
+                if_true,
+                if_false
+            },
+            Test {
+                divisible_by: 19,
+                if_true: 0,
+                if_false: 0
+            },
+        )
+    }
+
+    #[test]
+    fn invalid_notes() {
+        assert!(Day11::parse(&notes(23, 1, 1)).is_ok());
+        let error = |text: String| Day11::parse(&text).unwrap_err().to_string();
+        assert_eq!(
+            error(notes(0, 1, 1)),
+            "Parse error at line 4, column 22: can't test divisibility by 0"
+        );
+        assert_eq!(
+            error(notes(23, 0, 1)),
+            "Parse error at line 5, column 30: monkey throws to itself"
+        );
+        assert_eq!(
+            error(notes(23, 1, 7)),
+            "Parse error at line 6, column 31: no monkey 7, there are 2"
+        );
+
+        // A lone monkey can only throw to itself, so no input reaches the solver with fewer
+        // than two monkeys except an empty one
+        let none = Day11::parse("").unwrap();
+        assert!(matches!(
+            Day11::part1(&none, &()),
+            Err(AocError::NoSolution(_))
+        ));
+    }
+}
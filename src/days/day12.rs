@@ -0,0 +1,80 @@
+use crate::commons::grid::{parse_text, Grid, SingleVecGrid, TextGrid};
+use crate::commons::pathfinding::{Cell, Search};
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct HeightMap {
+    /// Elevation of each square, 0 for `a` up to 25 for `z`
+    heights: SingleVecGrid<u8>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+fn climbable((_, from): Cell<'_, SingleVecGrid<u8>>, (_, to): Cell<'_, SingleVecGrid<u8>>) -> bool {
+    *to <= from + 1
+}
+
+/// Fewest steps from any of `starts` to the best signal
+fn fewest_steps(
+    map: &HeightMap,
+    starts: impl IntoIterator<Item = (usize, usize)>,
+) -> Result<usize, AocError> {
+    Search::new(&map.heights)
+        .bfs(starts, climbable, |(c, _)| *c == map.end)
+        .goal_cost()
+        .ok_or_else(|| AocError::no_solution("end is unreachable"))
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HeightMap;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<HeightMap, AocError> {
+        let map: TextGrid<SingleVecGrid<u8>> = parse_text(input, &['S', 'E'], |c| {
+            let c = match c {
+                'S' => 'a',
+                'E' => 'z',
+                c => c,
+            };
+            if c.is_ascii_lowercase() {
+                Ok(c as u8 - b'a')
+            } else {
+                Err(format!("unexpected {:?}", c))
+            }
+        })?;
+
+        Ok(HeightMap {
+            start: *map
+                .marker('S')
+                .ok_or_else(|| AocError::parse(1, 1, "no start position"))?,
+            end: *map
+                .marker('E')
+                .ok_or_else(|| AocError::parse(1, 1, "no end position"))?,
+            heights: map.grid,
+        })
+    }
+
+    fn part1(input: &HeightMap, _params: &()) -> Result<usize, AocError> {
+        fewest_steps(input, [input.start])
+    }
+
+    fn part2(input: &HeightMap, _params: &()) -> Result<usize, AocError> {
+        let lowest = input
+            .heights
+            .points()
+            .filter(|(_, h)| **h == 0)
+            .map(|(c, _)| c);
+        fewest_steps(input, lowest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day12, "12");
+}
@@ -0,0 +1,198 @@
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+use itertools::Itertools;
+use std::fmt;
+use std::str::FromStr;
+
+peg::parser! {
+    grammar signal_parser() for str {
+        rule number() -> u8
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule number_enum() -> Signal
+            = n:number() { Signal::Number(n) }
+
+        rule signal_list() -> Signal
+            = "[" s:signal() ** "," "]"
+            {
+                Signal::List(s)
+        }
+
+        rule empty_signal() -> Signal
+            = "" {
+                Signal::Empty
+            }
+
+        pub rule signal() -> Signal
+            = signal_list() / number_enum();
+
+        pub rule signal_top() -> Signal
+            = signal() / empty_signal();
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Signal {
+    Number(u8),
+    List(Vec<Signal>),
+    Empty,
+}
+
+impl std::cmp::Ord for Signal {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.compare(other).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+
+impl std::cmp::PartialOrd for Signal {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Signal {
+    /// Compares two signals, returning `None` when the comparison is inconclusive
+    fn compare(&self, right: &Self) -> Option<std::cmp::Ordering> {
+        match (self, right) {
+            (Signal::Number(l), Signal::Number(r)) => match l.cmp(r) {
+                std::cmp::Ordering::Less => Some(std::cmp::Ordering::Less),
+                std::cmp::Ordering::Greater => Some(std::cmp::Ordering::Greater),
+                std::cmp::Ordering::Equal => None,
+            },
+            (Signal::List(l), Signal::List(r)) => {
+                let mut r_iter = r.iter();
+
+                for l in l.iter() {
+                    if let Some(r) = r_iter.next() {
+                        if let Some(res) = l.compare(r) {
+                            return Some(res);
+                        }
+                    } else {
+                        return Some(std::cmp::Ordering::Greater); // Right is shorter than length
+                    }
+                }
+
+                if r_iter.next().is_some() {
+                    Some(std::cmp::Ordering::Less) // r still has values, so is longer and orders before l
+                } else {
+                    None // Same length, not conclusive
+                }
+            }
+            (Signal::Number(l), Signal::List(_)) => {
+                let l_list = vec![Signal::Number(*l)];
+                Signal::List(l_list).compare(right)
+            }
+            (Signal::List(_), Signal::Number(r)) => {
+                let r_list = vec![Signal::Number(*r)];
+                self.compare(&Signal::List(r_list))
+            }
+            _ => panic!("Not handled {:?}", (self, right)),
+        }
+    }
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signal::Number(n) => write!(f, "{}", n),
+            Signal::List(l) => write!(f, "[{}]", l.iter().format(",")),
+            Signal::Empty => Ok(()),
+        }
+    }
+}
+
+impl FromStr for Signal {
+    type Err = peg::error::ParseError<peg::str::LineCol>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        signal_parser::signal_top(s)
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Signal>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Signal>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(input: &Vec<Signal>, _params: &()) -> Result<usize, AocError> {
+        let mut answer = 0;
+        let mut idx = 1;
+        let mut iter = input.iter();
+        while let Some(left) = iter.next() {
+            let right = iter
+                .next()
+                .ok_or_else(|| AocError::no_solution("signal without a pair"))?;
+            if *left == Signal::Empty || *right == Signal::Empty {
+                return Err(AocError::no_solution(format!("blank line in pair {}", idx)));
+            }
+
+            if left.cmp(right).is_lt() {
+                answer += idx;
+            }
+            // Discard empty
+            iter.next();
+            idx += 1;
+        }
+        Ok(answer)
+    }
+
+    fn part2(input: &Vec<Signal>, _params: &()) -> Result<usize, AocError> {
+        let mut input = input
+            .iter()
+            .filter(|x| **x != Signal::Empty)
+            .collect::<Vec<_>>();
+        let divider1 = Signal::List(vec![Signal::List(vec![Signal::Number(2)])]);
+        let divider2 = Signal::List(vec![Signal::List(vec![Signal::Number(6)])]);
+        input.push(&divider1);
+        input.push(&divider2);
+
+        input.sort();
+
+        let first_idx = input
+            .binary_search(&&divider1)
+            .map_err(|_| AocError::no_solution("first divider not found"))?;
+        let second_idx = input
+            .binary_search(&&divider2)
+            .map_err(|_| AocError::no_solution("second divider not found"))?;
+
+        Ok((first_idx + 1) * (second_idx + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day13, "13");
+
+    /// Any signal but `Empty`, which only stands for a blank line
+    fn signal() -> impl Strategy<Value = Signal> {
+        any::<u8>()
+            .prop_map(Signal::Number)
+            .prop_recursive(4, 32, 5, |inner| vec(inner, 0..5).prop_map(Signal::List))
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(signal in signal()) {
+            prop_assert_eq!(signal.to_string().parse::<Signal>().unwrap(), signal);
+        }
+
+        #[test]
+        fn solve_never_panics(lines in vec(prop_oneof![Just(Signal::Empty), signal()], 0..10)) {
+            let input = Day13::parse(&lines.iter().join("\n")).unwrap();
+            let _ = Day13::part1(&input, &());
+            let _ = Day13::part2(&input, &());
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::commons::grid::{DynamicGrid, Grid};
+use crate::error::AocError;
+use crate::params;
+use crate::solution::Solution;
+use itertools::Itertools;
+
+peg::parser! {
+    grammar probe_reports() for str {
+        rule number() -> isize
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule point() -> (isize, isize)
+            = l:number() "," r:number() {
+                (l, r)
+            }
+
+        pub rule path() -> Vec<(isize, isize)>
+            = point() ++ " -> "
+    }
+}
+
+/// Writes a path back out in the form the scan uses
+pub fn format_path(path: &[(isize, isize)]) -> String {
+    path.iter()
+        .map(|(x, y)| format!("{},{}", x, y))
+        .join(" -> ")
+}
+
+#[derive(Clone)]
+pub struct CaveGrid {
+    grid: DynamicGrid<bool>,
+    wall_bottom: isize,
+}
+
+impl CaveGrid {
+    pub fn new() -> Self {
+        Self {
+            grid: DynamicGrid::new(),
+            wall_bottom: isize::MIN,
+        }
+    }
+
+    fn from_paths(paths: &[Vec<(isize, isize)>]) -> Self {
+        let mut grid = CaveGrid::new();
+        for path in paths {
+            grid.load_path(path);
+        }
+        grid
+    }
+
+    pub fn populated(&self, point: &(isize, isize)) -> bool {
+        if point.1 >= self.wall_bottom + 2 {
+            true
+        } else {
+            self.grid.at(point).copied().unwrap_or(false)
+        }
+    }
+
+    pub fn populate(&mut self, point: (isize, isize)) {
+        self.grid.set(point, true);
+    }
+
+    pub fn load_path(&mut self, path: &[(isize, isize)]) {
+        let mut path_iter = path.iter();
+        let mut last = *path_iter.next().expect("path should be at least 1 long");
+        for n in path_iter {
+            let dir = ((n.0 - last.0).signum(), (n.1 - last.1).signum());
+            self.populate(*n);
+            self.wall_bottom = self.wall_bottom.max(n.1);
+
+            while last != *n {
+                self.populate(last);
+                self.wall_bottom = self.wall_bottom.max(last.1);
+                last = ((last.0 + dir.0), (last.1 + dir.1));
+            }
+        }
+    }
+}
+
+impl Default for CaveGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Where sand tries to move, in order: down, down and left, down and right
+const FALL: [(isize, isize); 3] = [(0, 1), (-1, 1), (1, 1)];
+
+fn fill_until_overflow(mut input: CaveGrid, sand_drop: (isize, isize)) -> Result<isize, AocError> {
+    // Sand only overflows past the lowest wall, so without one below the source it would
+    // pile up on the floor forever
+    if sand_drop.1 >= input.wall_bottom {
+        return Err(AocError::no_solution(format!(
+            "no rock below the source at {},{}",
+            sand_drop.0, sand_drop.1
+        )));
+    }
+    let mut placed = 0;
+
+    'outer: loop {
+        let mut sand_pos = sand_drop;
+        loop {
+            sand_pos = match FALL
+                .iter()
+                .map(|(dx, dy)| (sand_pos.0 + dx, sand_pos.1 + dy))
+                .find(|p| !input.populated(p))
+            {
+                Some(p) => p,
+                None => break,
+            };
+
+            if sand_pos.1 > input.wall_bottom {
+                break 'outer;
+            }
+        }
+        placed += 1;
+        input.populate(sand_pos);
+    }
+
+    Ok(placed)
+}
+
+fn fill_until_blocked(mut input: CaveGrid, sand_drop: (isize, isize)) -> isize {
+    let mut placed = 0;
+
+    'outer: loop {
+        let mut sand_pos = sand_drop;
+        loop {
+            sand_pos = match FALL
+                .iter()
+                .map(|(dx, dy)| (sand_pos.0 + dx, sand_pos.1 + dy))
+                .find(|p| !input.populated(p))
+            {
+                Some(p) => p,
+                None => break,
+            };
+        }
+        placed += 1;
+        if sand_pos == sand_drop {
+            break 'outer;
+        }
+        input.populate(sand_pos);
+    }
+
+    placed
+}
+
+params! {
+    pub struct Day14Params {
+        source_x: isize = 500,
+        source_y: isize = 0,
+    }
+}
+
+impl Day14Params {
+    fn source(&self) -> (isize, isize) {
+        (self.source_x, self.source_y)
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Vec<(isize, isize)>>;
+    type Params = Day14Params;
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Vec<Vec<(isize, isize)>>, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, s)| probe_reports::path(s).map_err(|e| AocError::from(e).on_line(i + 1)))
+            .collect()
+    }
+
+    fn part1(input: &Vec<Vec<(isize, isize)>>, params: &Day14Params) -> Result<isize, AocError> {
+        let grid = CaveGrid::from_paths(input);
+        fill_until_overflow(grid, params.source())
+    }
+
+    fn part2(input: &Vec<Vec<(isize, isize)>>, params: &Day14Params) -> Result<isize, AocError> {
+        let grid = CaveGrid::from_paths(input);
+        Ok(fill_until_blocked(grid, params.source()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day14, "14");
+
+    proptest! {
+        #[test]
+        fn round_trip(path in vec((0..1000isize, 0..1000isize), 1..10)) {
+            prop_assert_eq!(probe_reports::path(&format_path(&path)).unwrap(), path);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "([0-9]{1,3}|,| -> |\\n)*") {
+            let _ = Day14::parse(&s);
+        }
+    }
+
+    #[test]
+    fn rejects_empty_path() {
+        assert!(Day14::parse("498,4 -> 498,6\n\n").is_err());
+    }
+
+    #[test]
+    fn no_rock_below_source() {
+        let paths = Day14::parse("498,4 -> 498,6 -> 496,6\n").unwrap();
+        let below = Day14Params {
+            source_y: 1000,
+            ..Default::default()
+        };
+        assert!(matches!(
+            Day14::part1(&paths, &below),
+            Err(AocError::NoSolution(_))
+        ));
+        assert!(matches!(
+            Day14::part1(&Day14::parse("").unwrap(), &Default::default()),
+            Err(AocError::NoSolution(_))
+        ));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::commons::geom::Point;
+use crate::error::AocError;
+use crate::params;
+use crate::solution::Solution;
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Reading {
+    sensor: Point<isize>,
+    beacon: Point<isize>,
+}
+
+impl fmt::Display for Reading {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            self.sensor.x(),
+            self.sensor.y(),
+            self.beacon.x(),
+            self.beacon.y()
+        )
+    }
+}
+
+peg::parser! {
+    grammar sensor() for str {
+        rule number() -> isize
+            = n:$("-"? ['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule point() -> Point<isize>
+            = "x=" x:number() ", y=" y:number() {
+                Point::new(x, y)
+            }
+
+        pub rule reading() -> Reading
+            = "Sensor at " sensor:point() ": closest beacon is at " beacon:point() {
+                Reading{sensor, beacon}
+            }
+    }
+}
+
+struct Ranges {
+    ranges: Vec<(isize, isize)>,
+}
+
+impl Ranges {
+    pub fn new() -> Self {
+        Self {
+            ranges: Vec::with_capacity(1_000),
+        }
+    }
+
+    pub fn add(&mut self, range: (isize, isize)) {
+        self.ranges.push(range);
+    }
+
+    pub fn compat(&mut self) {
+        self.ranges.sort();
+        let (mut current_min, mut current_max) = match self.ranges.first() {
+            Some(&first) => first,
+            // No sensor reaches the row
+            None => return,
+        };
+        let mut new_ranges = Vec::with_capacity(self.ranges.len());
+        for range in &self.ranges {
+            let (min, max) = range;
+            if min - 1 <= current_max {
+                // This range extends/contains
+                current_min = current_min.min(*min);
+                current_max = current_max.max(*max);
+            } else {
+                // This is new, and doesn't extend current
+                new_ranges.push((current_min, current_max));
+                current_min = *min;
+                current_max = *max;
+            }
+        }
+        new_ranges.push((current_min, current_max));
+        self.ranges = new_ranges;
+    }
+}
+
+fn ranges_at_row(input: &[Reading], y: isize) -> Ranges {
+    let mut ranges = Ranges::new();
+    for reading in input {
+        let max_distance = reading.sensor.x().abs_diff(*reading.beacon.x())
+            + reading.sensor.y().abs_diff(*reading.beacon.y());
+
+        let distance = reading.sensor.y().abs_diff(y);
+        if distance <= max_distance {
+            let max_x_delta = distance.abs_diff(max_distance);
+            ranges.add((
+                reading.sensor.x() - max_x_delta as isize,
+                reading.sensor.x() + max_x_delta as isize,
+            ))
+        }
+    }
+    ranges.compat();
+    ranges
+}
+
+fn excluded_on_row(input: &[Reading], row: isize) -> usize {
+    let becons_on_row = input
+        .iter()
+        .filter_map(|x| {
+            if *x.beacon.y() == row {
+                Some(x.beacon.tuple_copy())
+            } else {
+                None
+            }
+        })
+        .collect::<HashSet<_>>();
+    let ranges = ranges_at_row(input, row);
+
+    ranges
+        .ranges
+        .iter()
+        .map(|x| x.0.abs_diff(x.1) + 1)
+        .sum::<usize>()
+        - becons_on_row.len()
+}
+
+fn tuning_frequency(input: &[Reading], max: usize) -> Result<usize, AocError> {
+    for y in 0..=max {
+        let mut ranges = Ranges::new();
+        for reading in input {
+            let max_distance = reading.sensor.x().abs_diff(*reading.beacon.x())
+                + reading.sensor.y().abs_diff(*reading.beacon.y());
+
+            let distance = reading.sensor.y().abs_diff(y as isize);
+            if distance <= max_distance {
+                let max_x_delta = distance.abs_diff(max_distance);
+                ranges.add((
+                    reading.sensor.x() - max_x_delta as isize,
+                    reading.sensor.x() + max_x_delta as isize,
+                ))
+            }
+        }
+        ranges.compat();
+        // The first column at or after 0 that no range covers
+        let mut x = 0;
+        for &(start, end) in &ranges.ranges {
+            if start > x {
+                break;
+            }
+            x = x.max(end + 1);
+        }
+        if x <= max as isize {
+            return Ok(x as usize * 4000000 + y);
+        }
+    }
+
+    Err(AocError::no_solution(
+        "no uncovered position in search area",
+    ))
+}
+
+params! {
+    pub struct Day15Params {
+        /// Row to count the positions without a beacon on
+        row: isize = 2_000_000,
+        /// Both coordinates of the distress beacon are between 0 and this
+        max: usize = 4_000_000,
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = Vec<Reading>;
+    type Params = Day15Params;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Reading>, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| sensor::reading(l).map_err(|e| AocError::from(e).on_line(i + 1)))
+            .collect()
+    }
+
+    fn part1(input: &Vec<Reading>, params: &Day15Params) -> Result<usize, AocError> {
+        Ok(excluded_on_row(input, params.row))
+    }
+
+    fn part2(input: &Vec<Reading>, params: &Day15Params) -> Result<usize, AocError> {
+        tuning_frequency(input, params.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commons::test_helpers::TestCase;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day15, "15", slow_part2: ["input"]);
+
+    #[test]
+    #[ignore = "part 2 scans four million rows, which is too slow for a debug build"]
+    fn input_part2() {
+        TestCase::from_answers("inputs/15", "answers/15").check::<Day15>();
+    }
+
+    #[test]
+    fn uncovered_rows() {
+        let readings = Day15::parse("Sensor at x=2, y=2: closest beacon is at x=3, y=2\n").unwrap();
+        let far = Day15Params {
+            row: 100_000_000,
+            max: 20,
+        };
+        assert_eq!(Day15::part1(&readings, &far).unwrap(), 0);
+        assert_eq!(Day15::part2(&readings, &far).unwrap(), 0);
+        assert_eq!(Day15::part1(&Vec::new(), &far).unwrap(), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(sx: isize, sy: isize, bx: isize, by: isize) {
+            let reading = Reading {
+                sensor: Point::new(sx, sy),
+                beacon: Point::new(bx, by),
+            };
+            prop_assert_eq!(sensor::reading(&reading.to_string()).unwrap(), reading);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "(Sensor at |: closest beacon is at |x=|, y=|-|[0-9]{1,3}|\\n)*") {
+            let _ = Day15::parse(&s);
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use aoc2022::commons::io::load_argv_lines;
+use crate::error::AocError;
+use crate::params;
+use crate::solution::Solution;
 use itertools::Itertools;
 use petgraph::algo::floyd_warshall;
 use petgraph::graph::NodeIndex;
 use petgraph::Graph;
 use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
-use std::error::Error;
+use std::fmt;
 
 peg::parser! {
     grammar valve_parser() for str {
@@ -15,7 +17,7 @@ peg::parser! {
             = n:$(['A'..='Z']+) { n.to_string() }
 
         rule valve_list() -> Vec<String>
-            = n:valve() ** ", " { n }
+            = n:valve() ++ ", " { n }
 
         pub rule valve_line() -> Valve
             = "Valve " v:valve() " has flow rate=" r:number() "; tunnel" "s"? " lead" "s"? " to valve" ("s "/" ") l:valve_list() {
@@ -27,14 +29,32 @@ peg::parser! {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct Valve {
     name: String,
     flow_rate: usize,
     connected_to: Vec<String>,
 }
 
-fn build_graph(valves: &[Valve]) -> Graph<&Valve, ()> {
+impl fmt::Display for Valve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tunnels = if self.connected_to.len() == 1 {
+            "tunnel leads to valve"
+        } else {
+            "tunnels lead to valves"
+        };
+        write!(
+            f,
+            "Valve {} has flow rate={}; {} {}",
+            self.name,
+            self.flow_rate,
+            tunnels,
+            self.connected_to.iter().format(", ")
+        )
+    }
+}
+
+fn build_graph(valves: &[Valve]) -> Result<Graph<&Valve, ()>, AocError> {
     let mut valve_map = HashMap::new();
     let mut g = Graph::new();
     for valve in valves {
@@ -45,12 +65,17 @@ fn build_graph(valves: &[Valve]) -> Graph<&Valve, ()> {
     for valve in valves {
         let src = valve_map.get(&valve.name).unwrap();
         for dst_name in &g.node_weight(*src).unwrap().connected_to {
-            let dst = valve_map.get(dst_name).unwrap();
+            let dst = valve_map.get(dst_name).ok_or_else(|| {
+                AocError::no_solution(format!(
+                    "valve {} has a tunnel to unknown valve {}",
+                    valve.name, dst_name
+                ))
+            })?;
             g.add_edge(*src, *dst, ());
         }
     }
 
-    g
+    Ok(g)
 }
 
 #[derive(Debug)]
@@ -103,16 +128,22 @@ fn max_flow<'a>(
     (max_flow_rate + from_flow, path)
 }
 
-fn part1(g: &Graph<&Valve, ()>) -> usize {
+fn find_valve(g: &Graph<&Valve, ()>, name: &str) -> Result<NodeIndex<u32>, AocError> {
+    g.node_indices()
+        .find(|idx| g.node_weight(*idx).unwrap().name == name)
+        .ok_or_else(|| AocError::no_solution(format!("no valve named {}", name)))
+}
+
+fn solo_max_flow(g: &Graph<&Valve, ()>, start: &str, minutes: usize) -> Result<usize, AocError> {
     let all_pairs = floyd_warshall(g, |_| 1).expect("Couldn't figure out paths");
     let mut worthwhile_valve_costs = HashMap::new();
     for ((from, to), v) in all_pairs {
         let from_valve = g.node_weight(from).expect("valve node");
-        if from_valve.flow_rate == 0 && from_valve.name != "AA" {
+        if from_valve.flow_rate == 0 && from_valve.name != start {
             continue;
         }
         let to_valve = g.node_weight(to).expect("valve node");
-        if to_valve.flow_rate == 0 && to_valve.name != "AA" {
+        if to_valve.flow_rate == 0 && to_valve.name != start {
             continue;
         }
         let e = worthwhile_valve_costs.entry(from);
@@ -120,13 +151,9 @@ fn part1(g: &Graph<&Valve, ()>) -> usize {
         map.insert(to, v);
     }
 
-    let start = g
-        .node_indices()
-        .find(|idx| g.node_weight(*idx).unwrap().name == "AA")
-        .unwrap();
-
-    let (flow, _) = max_flow(g, start, &worthwhile_valve_costs, &HashSet::new(), 30);
-    flow
+    let start = find_valve(g, start)?;
+    let (flow, _) = max_flow(g, start, &worthwhile_valve_costs, &HashSet::new(), minutes);
+    Ok(flow)
 }
 
 #[derive(Debug)]
@@ -137,12 +164,12 @@ struct FlowState {
     flow_at_end: usize,
 }
 
-fn part2(g: &Graph<&Valve, ()>) -> usize {
+fn paired_max_flow(g: &Graph<&Valve, ()>, start: &str, minutes: usize) -> Result<usize, AocError> {
     let all_pairs = floyd_warshall(g, |_| 1).expect("Couldn't figure out paths");
     let mut worthwhile_valve_costs = HashMap::new();
     for ((from, to), v) in all_pairs {
         let from_valve = g.node_weight(from).expect("valve node");
-        if from_valve.flow_rate == 0 && from_valve.name != "AA" {
+        if from_valve.flow_rate == 0 && from_valve.name != start {
             continue;
         }
         let to_valve = g.node_weight(to).expect("valve node");
@@ -154,17 +181,13 @@ fn part2(g: &Graph<&Valve, ()>) -> usize {
         map.insert(to, v);
     }
 
-    let start = g
-        .node_indices()
-        .find(|idx| g.node_weight(*idx).unwrap().name == "AA")
-        .unwrap();
-
+    let start = find_valve(g, start)?;
     let mut best_flow_for_valves_open: HashMap<BTreeSet<NodeIndex>, usize> = HashMap::new();
     let mut queue = VecDeque::with_capacity(1024);
     queue.push_back(FlowState {
         at: start,
         opened_valves: BTreeSet::new(),
-        remaining_minutes: 26,
+        remaining_minutes: minutes,
         flow_at_end: 0,
     });
 
@@ -204,19 +227,80 @@ fn part2(g: &Graph<&Valve, ()>) -> usize {
         .filter(|((p1, _), (p2, _))| p1.is_disjoint(p2))
         .map(|((_, p1), (_, p2))| p1 + p2)
         .max()
-        .unwrap()
+        .ok_or_else(|| AocError::no_solution("no disjoint pair of valve sets"))
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let input: Vec<String> = load_argv_lines().collect::<Result<_, _>>()?;
-    let input = input
-        .iter()
-        .map(|s| valve_parser::valve_line(s))
-        .collect::<Result<Vec<_>, _>>()?;
-    let g = build_graph(&input);
+params! {
+    pub struct Day16Params {
+        minutes: usize = 30,
+        /// Minutes left after teaching the elephant
+        paired_minutes: usize = 26,
+        start: String = "AA".to_string(),
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<Valve>;
+    type Params = Day16Params;
+    type Part1 = usize;
+    type Part2 = usize;
 
-    println!("{}", part1(&g));
-    println!("{}", part2(&g));
+    fn parse(input: &str) -> Result<Vec<Valve>, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| valve_parser::valve_line(l).map_err(|e| AocError::from(e).on_line(i + 1)))
+            .collect()
+    }
+
+    fn part1(input: &Vec<Valve>, params: &Day16Params) -> Result<usize, AocError> {
+        solo_max_flow(&build_graph(input)?, &params.start, params.minutes)
+    }
+
+    fn part2(input: &Vec<Valve>, params: &Day16Params) -> Result<usize, AocError> {
+        paired_max_flow(&build_graph(input)?, &params.start, params.paired_minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day16, "16");
 
-    Ok(())
+    #[test]
+    fn unknown_tunnel() {
+        let input = Day16::parse(
+            "Valve AA has flow rate=0; tunnels lead to valves BB, CC\n\
+             Valve BB has flow rate=3; tunnel leads to valve AA\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Day16::part1(&input, &Day16Params::default())
+                .unwrap_err()
+                .to_string(),
+            "No solution found: valve AA has a tunnel to unknown valve CC"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            name in "[A-Z]{1,3}",
+            flow_rate: usize,
+            connected_to in vec("[A-Z]{1,3}", 1..5),
+        ) {
+            let valve = Valve { name, flow_rate, connected_to };
+            prop_assert_eq!(valve_parser::valve_line(&valve.to_string()).unwrap(), valve);
+        }
+
+        #[test]
+        fn parse_never_panics(s in "(Valve |[A-Z]{1,2}| has flow rate=|[0-9]{1,3}|; |tunnels? |leads? |to |valves? |, |\\n)*") {
+            let _ = Day16::parse(&s);
+        }
+    }
 }
@@ -0,0 +1,327 @@
+use crate::commons::io::{parse_borrowed_lines, FromBorrowedStr};
+use crate::error::AocError;
+use crate::solution::Solution;
+use std::collections::HashMap;
+use std::fmt;
+
+peg::parser! {
+    grammar monkey_parser() for str {
+        rule name() -> &'input str
+            = $(['a'..='z']+)
+
+        rule number() -> isize
+            = n:$(['0'..='9']+) {? n.parse().or(Err("bad number")) }
+
+        rule operator() -> Operator
+            = " + " { Operator::Add }
+            / " - " { Operator::Sub }
+            / " * " { Operator::Mul }
+            / " / " { Operator::Div }
+
+        rule job() -> Job<&'input str>
+            = n:number() { Job::Number(n) }
+            / l:name() op:operator() r:name() { Job::Operation(l, op, r) }
+
+        pub rule assignment() -> Assignment<'input>
+            = name:name() ": " job:job() { Assignment { name, job } }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+impl Operator {
+    fn apply(self, l: isize, r: isize) -> Result<isize, AocError> {
+        let result = match self {
+            Self::Add => l.checked_add(r),
+            Self::Sub => l.checked_sub(r),
+            Self::Mul => l.checked_mul(r),
+            Self::Div => l.checked_div(r),
+        };
+        result.ok_or_else(|| AocError::no_solution(format!("can't work out {} {} {}", l, self, r)))
+    }
+
+    /// The left operand that gives `result` with `r` on the right
+    fn solve_left(self, result: isize, r: isize) -> Result<isize, AocError> {
+        match self {
+            Self::Add => Self::Sub.apply(result, r),
+            Self::Sub => Self::Add.apply(result, r),
+            Self::Mul => Self::Div.apply(result, r),
+            Self::Div => Self::Mul.apply(result, r),
+        }
+    }
+
+    /// The right operand that gives `result` with `l` on the left
+    fn solve_right(self, result: isize, l: isize) -> Result<isize, AocError> {
+        match self {
+            Self::Add => Self::Sub.apply(result, l),
+            Self::Sub => Self::Sub.apply(l, result),
+            Self::Mul => Self::Div.apply(result, l),
+            Self::Div => Self::Div.apply(l, result),
+        }
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::Add => '+',
+            Self::Sub => '-',
+            Self::Mul => '*',
+            Self::Div => '/',
+        };
+        write!(f, "{}", c)
+    }
+}
+
+/// What a monkey shouts, with the monkeys it waits on given as `M`: their names as parsed,
+/// then their index once every name is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Job<M> {
+    Number(isize),
+    Operation(M, Operator, M),
+}
+
+/// One line of the input, borrowing the monkeys' names from it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Assignment<'a> {
+    name: &'a str,
+    job: Job<&'a str>,
+}
+
+impl<'a> FromBorrowedStr<'a> for Assignment<'a> {
+    type Err = AocError;
+
+    fn from_borrowed_str(s: &'a str) -> Result<Self, Self::Err> {
+        Ok(monkey_parser::assignment(s)?)
+    }
+}
+
+impl fmt::Display for Assignment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.job {
+            Job::Number(n) => write!(f, "{}: {}", self.name, n),
+            Job::Operation(l, op, r) => write!(f, "{}: {} {} {}", self.name, l, op, r),
+        }
+    }
+}
+
+/// Every monkey's job, indexed by the order they're listed in
+#[derive(Debug)]
+pub struct Monkeys {
+    jobs: Vec<Job<usize>>,
+    root: Option<usize>,
+    humn: Option<usize>,
+}
+
+impl Monkeys {
+    fn root(&self) -> Result<usize, AocError> {
+        self.root
+            .ok_or_else(|| AocError::no_solution("no root monkey"))
+    }
+
+    fn value(&self, monkey: usize) -> Result<isize, AocError> {
+        match self.jobs[monkey] {
+            Job::Number(n) => Ok(n),
+            Job::Operation(l, op, r) => op.apply(self.value(l)?, self.value(r)?),
+        }
+    }
+
+    fn waits_on_humn(&self, monkey: usize) -> bool {
+        Some(monkey) == self.humn
+            || match self.jobs[monkey] {
+                Job::Number(_) => false,
+                Job::Operation(l, _, r) => self.waits_on_humn(l) || self.waits_on_humn(r),
+            }
+    }
+
+    /// What humn must shout for `monkey` to shout `want`
+    fn solve_for_humn(&self, monkey: usize, want: isize) -> Result<isize, AocError> {
+        if Some(monkey) == self.humn {
+            return Ok(want);
+        }
+        match self.jobs[monkey] {
+            Job::Operation(l, op, r) if self.waits_on_humn(l) => {
+                self.solve_for_humn(l, op.solve_left(want, self.value(r)?)?)
+            }
+            Job::Operation(l, op, r) => {
+                self.solve_for_humn(r, op.solve_right(want, self.value(l)?)?)
+            }
+            Job::Number(_) => Err(AocError::no_solution("no monkey waits on humn")),
+        }
+    }
+}
+
+/// A monkey whose answer depends on itself, if any. Peels off monkeys whose inputs are all
+/// known, as in a topological sort; every monkey left waits on at least one other that's
+/// left, so following those must come back round to a monkey on a cycle.
+fn find_cycle(jobs: &[Job<usize>]) -> Option<usize> {
+    let mut waiting_on = vec![0; jobs.len()];
+    let mut dependents = vec![Vec::new(); jobs.len()];
+    for (monkey, job) in jobs.iter().enumerate() {
+        if let Job::Operation(l, _, r) = *job {
+            for input in [l, r] {
+                waiting_on[monkey] += 1;
+                dependents[input].push(monkey);
+            }
+        }
+    }
+
+    let mut ready = (0..jobs.len())
+        .filter(|&m| waiting_on[m] == 0)
+        .collect::<Vec<_>>();
+    while let Some(monkey) = ready.pop() {
+        for &dependent in &dependents[monkey] {
+            waiting_on[dependent] -= 1;
+            if waiting_on[dependent] == 0 {
+                ready.push(dependent);
+            }
+        }
+    }
+
+    let mut monkey = waiting_on.iter().position(|&n| n > 0)?;
+    let mut seen = vec![false; jobs.len()];
+    while !seen[monkey] {
+        seen[monkey] = true;
+        monkey = match jobs[monkey] {
+            Job::Operation(l, _, _) if waiting_on[l] > 0 => l,
+            Job::Operation(_, _, r) => r,
+            Job::Number(_) => unreachable!("monkeys with numbers are never left waiting"),
+        };
+    }
+    Some(monkey)
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Monkeys;
+    type Params = ();
+    type Part1 = isize;
+    type Part2 = isize;
+
+    fn parse(input: &str) -> Result<Monkeys, AocError> {
+        let assignments =
+            parse_borrowed_lines::<Assignment>(input).collect::<Result<Vec<_>, _>>()?;
+        let index = assignments
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a.name, i))
+            .collect::<HashMap<_, _>>();
+        let lookup = |line: usize, name: &str| {
+            index
+                .get(name)
+                .copied()
+                .ok_or_else(|| AocError::parse(line + 1, 1, format!("no monkey named {}", name)))
+        };
+        let jobs = assignments
+            .iter()
+            .enumerate()
+            .map(|(line, a)| match a.job {
+                Job::Number(n) => Ok(Job::Number(n)),
+                Job::Operation(l, op, r) => {
+                    Ok(Job::Operation(lookup(line, l)?, op, lookup(line, r)?))
+                }
+            })
+            .collect::<Result<Vec<_>, AocError>>()?;
+        if let Some(monkey) = find_cycle(&jobs) {
+            return Err(AocError::parse(
+                monkey + 1,
+                1,
+                format!(
+                    "monkey {} waits on its own answer",
+                    assignments[monkey].name
+                ),
+            ));
+        }
+
+        Ok(Monkeys {
+            jobs,
+            root: index.get("root").copied(),
+            humn: index.get("humn").copied(),
+        })
+    }
+
+    fn part1(monkeys: &Monkeys, _params: &()) -> Result<isize, AocError> {
+        monkeys.value(monkeys.root()?)
+    }
+
+    fn part2(monkeys: &Monkeys, _params: &()) -> Result<isize, AocError> {
+        if monkeys.humn.is_none() {
+            return Err(AocError::no_solution("no humn monkey"));
+        }
+        // Root checks its two numbers match, so whichever side humn is on must equal the other
+        match monkeys.jobs[monkeys.root()?] {
+            Job::Operation(l, _, r) if monkeys.waits_on_humn(l) => {
+                monkeys.solve_for_humn(l, monkeys.value(r)?)
+            }
+            Job::Operation(l, _, r) => monkeys.solve_for_humn(r, monkeys.value(l)?),
+            Job::Number(_) => Err(AocError::no_solution("root doesn't compare two monkeys")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    crate::solution_tests!(Day21, "21");
+
+    fn operator() -> impl Strategy<Value = Operator> {
+        prop_oneof![
+            Just(Operator::Add),
+            Just(Operator::Sub),
+            Just(Operator::Mul),
+            Just(Operator::Div),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip(
+            name in "[a-z]{1,4}",
+            number in 0..=isize::MAX,
+            l in "[a-z]{1,4}",
+            op in operator(),
+            r in "[a-z]{1,4}",
+        ) {
+            for job in [Job::Number(number), Job::Operation(l.as_str(), op, r.as_str())] {
+                let assignment = Assignment { name: &name, job };
+                let line = assignment.to_string();
+                prop_assert_eq!(Assignment::from_borrowed_str(&line).unwrap(), assignment);
+            }
+        }
+
+        #[test]
+        fn parse_never_panics(s in "([a-z]{1,4}|: |[0-9]{1,3}| [-+*/] |\\n)*") {
+            let _ = Day21::parse(&s);
+        }
+    }
+
+    #[test]
+    fn cycles() {
+        let err =
+            Day21::parse("root: abcd + humn\nabcd: efgh * efgh\nefgh: abcd - humn\nhumn: 5\n")
+                .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 1: monkey abcd waits on its own answer"
+        );
+        assert!(Day21::parse("root: root + root\n").is_err());
+    }
+
+    #[test]
+    fn unknown_monkey() {
+        let err = Day21::parse("root: abcd + efgh\nabcd: 3\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 1, column 1: no monkey named efgh"
+        );
+    }
+}
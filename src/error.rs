@@ -0,0 +1,99 @@
+use crate::params::ParamError;
+use peg::error::ParseError;
+use peg::str::LineCol;
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::io;
+use std::num::ParseIntError;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AocError {
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Missing input: {}", .0.display())]
+    MissingInput(PathBuf),
+    #[error("Parse error at line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    #[error("No solution found: {0}")]
+    NoSolution(String),
+    #[error(transparent)]
+    Param(#[from] ParamError),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Display) -> Self {
+        AocError::Parse {
+            line,
+            column,
+            message: message.to_string(),
+        }
+    }
+
+    pub fn no_solution(message: impl Display) -> Self {
+        AocError::NoSolution(message.to_string())
+    }
+
+    /// Moves a parse error reported against a single line to that line's place in the whole input
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            AocError::Parse {
+                line: inner,
+                column,
+                message,
+            } => AocError::Parse {
+                line: line + inner - 1,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+impl From<ParseError<LineCol>> for AocError {
+    fn from(e: ParseError<LineCol>) -> Self {
+        AocError::parse(
+            e.location.line,
+            e.location.column,
+            format!("expected {}", e.expected),
+        )
+    }
+}
+
+impl From<Infallible> for AocError {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+impl From<ParseIntError> for AocError {
+    fn from(e: ParseIntError) -> Self {
+        AocError::parse(1, 1, e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    peg::parser! {
+        grammar digits() for str {
+            pub rule digits() = ['0'..='9']+ "\n" ['0'..='9']+
+        }
+    }
+
+    #[test]
+    fn peg_position() {
+        let err: AocError = digits::digits("12\n3x").unwrap_err().into();
+        match err.on_line(10) {
+            AocError::Parse { line, column, .. } => assert_eq!((line, column), (11, 2)),
+            other => panic!("Unexpected error {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2022;
+
+/// Where the session token is read from when `AOC_SESSION` isn't set
+pub const SESSION_FILE: &str = ".aoc-session";
+
+// Be polite to the Advent of Code servers, see https://www.reddit.com/r/adventofcode/wiki/faqs/automation
+const USER_AGENT: &str = "github.com/niax/aoc-2022 input fetcher";
+const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error("No session token: set AOC_SESSION or write it to {SESSION_FILE}")]
+    MissingSession,
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Request to {0} failed with status {1}")]
+    Status(String, u16),
+    #[error("Request failed: {0}")]
+    Transport(String),
+    #[error("{} differs from the downloaded input, not overwriting it", .0.display())]
+    Changed(PathBuf),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    /// The input was already on disk, so no request was made
+    Cached,
+    Downloaded,
+    /// Re-downloaded and identical to the file on disk
+    Unchanged,
+}
+
+pub struct Fetcher {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Fetcher {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Fetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            interval: MIN_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Reads the session from `AOC_SESSION` or `SESSION_FILE`, and the base URL from
+    /// `AOC_BASE_URL` unless one is given
+    pub fn from_env(base_url: Option<String>) -> Result<Self, FetchError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(s) => s,
+            Err(_) => match fs::read_to_string(SESSION_FILE) {
+                Ok(s) => s,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(FetchError::MissingSession)
+                }
+                Err(e) => return Err(e.into()),
+            },
+        };
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(FetchError::MissingSession);
+        }
+
+        let base_url = base_url
+            .or_else(|| env::var("AOC_BASE_URL").ok())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        Ok(Fetcher::new(&base_url, session))
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn input_url(&self, day: u8) -> String {
+        format!("{}/{}/day/{}/input", self.base_url, YEAR, day)
+    }
+
+    /// Downloads a day's input, waiting first if the previous request was too recent
+    pub fn download(&mut self, day: u8) -> Result<String, FetchError> {
+        if let Some(last) = self.last_request {
+            let since = last.elapsed();
+            if since < self.interval {
+                thread::sleep(self.interval - since);
+            }
+        }
+        self.last_request = Some(Instant::now());
+
+        let url = self.input_url(day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| match e {
+                ureq::Error::Status(status, _) => FetchError::Status(url.clone(), status),
+                ureq::Error::Transport(t) => FetchError::Transport(t.to_string()),
+            })?;
+        Ok(response.into_string()?)
+    }
+
+    /// Makes sure `path` holds the day's input. An existing file is only compared against
+    /// a fresh download when `refresh` is set, and is never overwritten.
+    pub fn fetch(
+        &mut self,
+        day: u8,
+        path: impl AsRef<Path>,
+        refresh: bool,
+    ) -> Result<Outcome, FetchError> {
+        let path = path.as_ref();
+        let existing = match fs::read_to_string(path) {
+            Ok(s) => Some(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        match existing {
+            Some(_) if !refresh => Ok(Outcome::Cached),
+            Some(existing) => {
+                if self.download(day)? == existing {
+                    Ok(Outcome::Unchanged)
+                } else {
+                    Err(FetchError::Changed(path.to_path_buf()))
+                }
+            }
+            None => {
+                let input = self.download(day)?;
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                fs::write(path, input)?;
+                Ok(Outcome::Downloaded)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+
+    /// Serves `body` to each of `requests` connections, returning the request lines seen
+    fn stub_server(
+        requests: usize,
+        body: &'static str,
+    ) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut seen = Vec::new();
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    seen.push(line.trim().to_string());
+                }
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            seen
+        });
+        (url, handle)
+    }
+
+    #[test]
+    fn fetch_from_stub() {
+        let (url, server) = stub_server(2, "1\n2\n");
+        let dir = env::temp_dir().join(format!("aoc-fetch-{}", std::process::id()));
+        let path = dir.join("01");
+        let mut fetcher = Fetcher::new(&url, "secret").with_interval(Duration::ZERO);
+
+        assert_eq!(fetcher.fetch(1, &path, false).unwrap(), Outcome::Downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1\n2\n");
+        assert_eq!(fetcher.fetch(1, &path, false).unwrap(), Outcome::Cached);
+
+        fs::write(&path, "edited\n").unwrap();
+        assert!(matches!(
+            fetcher.fetch(1, &path, true),
+            Err(FetchError::Changed(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "edited\n");
+
+        let seen = server.join().unwrap();
+        assert!(seen.contains(&"GET /2022/day/1/input HTTP/1.1".to_string()));
+        assert!(seen.contains(&"Cookie: session=secret".to_string()));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
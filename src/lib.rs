@@ -1 +1,9 @@
+pub mod bench;
 pub mod commons;
+pub mod days;
+pub mod error;
+pub mod fetch;
+pub mod params;
+pub mod scaffold;
+pub mod solution;
+pub mod verify;
@@ -0,0 +1,191 @@
+use crate::commons::io::read_input;
+use crate::error::AocError;
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Extension of the file holding parameter overrides for an input, e.g. `15.sample.params`
+pub const PARAMS_SUFFIX: &str = ".params";
+
+#[derive(Error, Debug)]
+pub enum ParamError {
+    #[error("Unknown parameter {name} (expected one of: {known})")]
+    Unknown { name: String, known: String },
+    #[error("Bad value {value:?} for parameter {name}: {message}")]
+    Invalid {
+        name: String,
+        value: String,
+        message: String,
+    },
+    #[error("Expected name=value, found {0:?}")]
+    Malformed(String),
+}
+
+impl ParamError {
+    pub fn unknown(name: &str, known: &[&str]) -> Self {
+        ParamError::Unknown {
+            name: name.to_string(),
+            known: if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            },
+        }
+    }
+
+    pub fn invalid(name: &str, value: &str, message: impl Display) -> Self {
+        ParamError::Invalid {
+            name: name.to_string(),
+            value: value.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// Values a solver needs that can differ between inputs, such as the row day 15 scans,
+/// which is smaller for the sample. `Default` gives the values for the real puzzle input.
+/// Usually declared with `params!`.
+pub trait Params: Default + 'static {
+    const NAMES: &'static [&'static str];
+
+    /// Overrides a value by name, from `--param name=value` or a params file
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ParamError>;
+
+    /// The defaults with `overrides` applied in order
+    fn with_overrides(overrides: &[(String, String)]) -> Result<Self, ParamError> {
+        let mut params = Self::default();
+        for (name, value) in overrides {
+            params.set(name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+impl Params for () {
+    const NAMES: &'static [&'static str] = &[];
+
+    fn set(&mut self, name: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::unknown(name, Self::NAMES))
+    }
+}
+
+/// Declares a `Params` struct, each field given with its default
+///
+/// ```ignore
+/// params! {
+///     pub struct Day15Params {
+///         row: isize = 2_000_000,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $( $(#[$field_meta:meta])* $field:ident: $ty:ty = $default:expr, )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $( $(#[$field_meta])* pub $field: $ty, )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $( $field: $default, )*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn set(&mut self, name: &str, value: &str) -> Result<(), $crate::params::ParamError> {
+                match name {
+                    $(
+                        stringify!($field) => {
+                            self.$field = value
+                                .parse()
+                                .map_err(|e| $crate::params::ParamError::invalid(name, value, e))?;
+                        }
+                    )*
+                    _ => return Err($crate::params::ParamError::unknown(name, Self::NAMES)),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Splits a `name=value` override
+pub fn parse_override(s: &str) -> Result<(String, String), ParamError> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| ParamError::Malformed(s.to_string()))?;
+    Ok((name.trim().to_string(), value.trim().to_string()))
+}
+
+/// Reads `name = value` lines, skipping blank lines and `#` comments
+pub fn parse_params_file(text: &str) -> Result<Vec<(String, String)>, ParamError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(parse_override)
+        .collect()
+}
+
+pub fn sidecar_path(input_path: impl AsRef<Path>) -> PathBuf {
+    let mut path = input_path.as_ref().as_os_str().to_owned();
+    path.push(PARAMS_SUFFIX);
+    path.into()
+}
+
+/// The overrides in the `.params` file beside an input, if there is one
+pub fn load_sidecar(input_path: impl AsRef<Path>) -> Result<Vec<(String, String)>, AocError> {
+    let path = sidecar_path(input_path);
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+    Ok(parse_params_file(&read_input(path)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        pub struct TestParams {
+            row: isize = 2_000_000,
+            start: String = "AA".to_string(),
+        }
+    }
+
+    #[test]
+    fn overrides() {
+        let overrides = parse_params_file("# sample\nrow = 10\n\nstart=BB\n").unwrap();
+        let params = TestParams::with_overrides(&overrides).unwrap();
+        assert_eq!(
+            params,
+            TestParams {
+                row: 10,
+                start: "BB".to_string()
+            }
+        );
+
+        assert!(matches!(
+            TestParams::with_overrides(&[("row".to_string(), "ten".to_string())]),
+            Err(ParamError::Invalid { .. })
+        ));
+        assert_eq!(
+            TestParams::default()
+                .set("rows", "1")
+                .unwrap_err()
+                .to_string(),
+            "Unknown parameter rows (expected one of: row, start)"
+        );
+        assert!(parse_override("row").is_err());
+    }
+}
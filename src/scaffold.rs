@@ -0,0 +1,127 @@
+use std::fs::{self, OpenOptions};
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const TEMPLATE: &str = include_str!("../templates/day.rs");
+
+#[derive(Error, Debug)]
+pub enum ScaffoldError {
+    #[error("{} already exists", .0.display())]
+    Exists(PathBuf),
+    #[error("Day {0} is already registered in src/days/mod.rs")]
+    Registered(u8),
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+pub fn render_template(day: u8) -> String {
+    TEMPLATE.replace("{{NN}}", &format!("{:02}", day))
+}
+
+/// Adds the day's `pub mod` line and registry entry to the contents of `src/days/mod.rs`,
+/// keeping both in day order
+pub fn register(mod_rs: &str, day: u8) -> Result<String, ScaffoldError> {
+    let module = format!("pub mod day{:02};", day);
+    let entry = format!("        Day::new::<day{:02}::Day{:02}>({}),", day, day, day);
+    if mod_rs.lines().any(|l| l == module) {
+        return Err(ScaffoldError::Registered(day));
+    }
+
+    let mut lines = mod_rs.lines().map(str::to_string).collect::<Vec<_>>();
+    insert_sorted(&mut lines, "pub mod day", module);
+    insert_sorted(&mut lines, "        Day::new::<day", entry);
+
+    let mut out = lines.join("\n");
+    out.push('\n');
+    Ok(out)
+}
+
+/// Inserts `line` among the run of lines starting with `prefix`. Zero-padded day numbers
+/// mean string order is day order.
+fn insert_sorted(lines: &mut Vec<String>, prefix: &str, line: String) {
+    let first = lines.iter().position(|l| l.starts_with(prefix));
+    let at = match first {
+        Some(first) => lines[first..]
+            .iter()
+            .position(|l| !l.starts_with(prefix) || **l > *line)
+            .map_or(lines.len(), |i| first + i),
+        None => lines.len(),
+    };
+    lines.insert(at, line);
+}
+
+fn create_new(path: &Path, contents: &str) -> Result<(), ScaffoldError> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::AlreadyExists => ScaffoldError::Exists(path.to_path_buf()),
+            _ => e.into(),
+        })?;
+    io::Write::write_all(&mut file, contents.as_bytes())?;
+    Ok(())
+}
+
+/// Generates the solver module for `day` under `root` and registers it with the runner.
+/// Input and answer files that are already there, say from `aoc fetch`, are left alone.
+/// Returns the files created.
+pub fn scaffold(root: &Path, day: u8) -> Result<Vec<PathBuf>, ScaffoldError> {
+    let mod_path = root.join("src/days/mod.rs");
+    let registered = register(&fs::read_to_string(&mod_path)?, day)?;
+
+    let module = root.join(format!("src/days/day{:02}.rs", day));
+    create_new(&module, &render_template(day))?;
+    fs::write(&mod_path, registered)?;
+
+    let mut created = vec![module];
+    for path in [
+        format!("inputs/{:02}", day),
+        format!("inputs/extra/{:02}.sample", day),
+        format!("inputs/extra/{:02}.sample.answer", day),
+        format!("answers/{:02}", day),
+    ] {
+        let path = root.join(path);
+        match create_new(&path, "") {
+            Ok(()) => created.push(path),
+            Err(ScaffoldError::Exists(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registers_in_order() {
+        let mod_rs = "use crate::solution::Day;
+
+pub mod day01;
+pub mod day21;
+
+pub fn all() -> Vec<Day> {
+    vec![
+        Day::new::<day01::Day01>(1),
+        Day::new::<day21::Day21>(21),
+    ]
+}
+";
+        let out = register(mod_rs, 17).unwrap();
+        assert!(out.contains("pub mod day01;\npub mod day17;\npub mod day21;\n"));
+        assert!(out.contains(
+            "(1),\n        Day::new::<day17::Day17>(17),\n        Day::new::<day21::Day21>(21),"
+        ));
+        assert!(matches!(
+            register(&out, 17),
+            Err(ScaffoldError::Registered(17))
+        ));
+        assert!(render_template(17).contains("pub struct Day17;"));
+    }
+}
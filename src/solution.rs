@@ -0,0 +1,98 @@
+use crate::error::AocError;
+use crate::params::{ParamError, Params};
+use std::any::Any;
+use std::fmt::Display;
+
+pub trait Solution {
+    type Input: 'static;
+    /// Values that can differ between inputs, `()` when the puzzle has none
+    type Params: Params;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input, params: &Self::Params) -> Result<Self::Part1, AocError>;
+    fn part2(input: &Self::Input, params: &Self::Params) -> Result<Self::Part2, AocError>;
+}
+
+type ParseFn = fn(&str) -> Result<Box<dyn Any>, AocError>;
+type ParamsFn = fn(&[(String, String)]) -> Result<Box<dyn Any>, ParamError>;
+type PartFn = fn(&dyn Any, &dyn Any) -> Result<String, AocError>;
+
+/// A type-erased `Solution`, so every day can be stored in one registry.
+pub struct Day {
+    number: u8,
+    param_names: &'static [&'static str],
+    parse: ParseFn,
+    params: ParamsFn,
+    part1: PartFn,
+    part2: PartFn,
+}
+
+impl Day {
+    pub fn new<S: Solution>(number: u8) -> Self {
+        Day {
+            number,
+            param_names: S::Params::NAMES,
+            parse: |s| S::parse(s).map(|input| Box::new(input) as Box<dyn Any>),
+            params: |overrides| {
+                S::Params::with_overrides(overrides).map(|p| Box::new(p) as Box<dyn Any>)
+            },
+            part1: |input, params| {
+                S::part1(
+                    input.downcast_ref().expect("Input type"),
+                    params.downcast_ref().expect("Params type"),
+                )
+                .map(|a| a.to_string())
+            },
+            part2: |input, params| {
+                S::part2(
+                    input.downcast_ref().expect("Input type"),
+                    params.downcast_ref().expect("Params type"),
+                )
+                .map(|a| a.to_string())
+            },
+        }
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn param_names(&self) -> &'static [&'static str] {
+        self.param_names
+    }
+
+    pub fn input_path(&self) -> String {
+        format!("inputs/{:02}", self.number)
+    }
+
+    pub fn answers_path(&self) -> String {
+        format!("answers/{:02}", self.number)
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Box<dyn Any>, AocError> {
+        (self.parse)(input)
+    }
+
+    /// The day's default params with `overrides` applied
+    pub fn params(&self, overrides: &[(String, String)]) -> Result<Box<dyn Any>, AocError> {
+        Ok((self.params)(overrides)?)
+    }
+
+    pub fn part1(&self, input: &dyn Any, params: &dyn Any) -> Result<String, AocError> {
+        (self.part1)(input, params)
+    }
+
+    pub fn part2(&self, input: &dyn Any, params: &dyn Any) -> Result<String, AocError> {
+        (self.part2)(input, params)
+    }
+
+    pub fn run(&self, input: &str, params: &dyn Any) -> Result<(String, String), AocError> {
+        let parsed = self.parse(input)?;
+        Ok((
+            self.part1(parsed.as_ref(), params)?,
+            self.part2(parsed.as_ref(), params)?,
+        ))
+    }
+}
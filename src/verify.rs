@@ -0,0 +1,177 @@
+use crate::commons::io::read_input;
+use crate::error::AocError;
+use crate::params;
+use crate::solution::Day;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Status::Pass => "pass",
+            Status::Fail => "FAIL",
+            Status::Missing => "missing",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub expected: Option<String>,
+    pub actual: String,
+}
+
+impl PartResult {
+    pub fn status(&self) -> Status {
+        match &self.expected {
+            None => Status::Missing,
+            Some(e) if *e == self.actual => Status::Pass,
+            Some(_) => Status::Fail,
+        }
+    }
+}
+
+/// Directory of sample and alternate inputs, each named `NN.<name>`
+pub const EXTRA_INPUTS: &str = "inputs/extra";
+
+/// Extension of the file holding the answers for an extra input, e.g. `05.sample.answer`
+pub const ANSWER_SUFFIX: &str = ".answer";
+
+/// An input of a day together with its expected answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Case {
+    /// `input` for the real puzzle input, otherwise the extra input's name
+    pub name: String,
+    pub input_path: PathBuf,
+    pub answers_path: PathBuf,
+}
+
+pub fn sidecar_path(input_path: impl AsRef<Path>) -> PathBuf {
+    let mut path = input_path.as_ref().as_os_str().to_owned();
+    path.push(ANSWER_SUFFIX);
+    path.into()
+}
+
+/// Every input of `day` that has answers on disk: the real input first, then the extra
+/// inputs in name order
+pub fn cases(day: &Day) -> Vec<Case> {
+    let mut cases = Vec::new();
+    let (input_path, answers_path) = (PathBuf::from(day.input_path()), day.answers_path());
+    if input_path.is_file() && Path::new(&answers_path).is_file() {
+        cases.push(Case {
+            name: "input".to_string(),
+            input_path,
+            answers_path: answers_path.into(),
+        });
+    }
+
+    let prefix = format!("{:02}.", day.number());
+    let mut extras = fs::read_dir(EXTRA_INPUTS)
+        .map(|dir| {
+            dir.filter_map(|e| e.ok())
+                .filter_map(|e| e.file_name().into_string().ok())
+                .filter(|f| f.starts_with(&prefix) && !f.ends_with(ANSWER_SUFFIX))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    extras.sort();
+    for file in extras {
+        let input_path = Path::new(EXTRA_INPUTS).join(&file);
+        let answers_path = sidecar_path(&input_path);
+        if answers_path.is_file() {
+            cases.push(Case {
+                name: file[prefix.len()..].to_string(),
+                input_path,
+                answers_path,
+            });
+        }
+    }
+    cases
+}
+
+/// Expected answers, one per line, in part order. A blank line is a part with no answer
+/// yet.
+pub fn load_answers(path: impl AsRef<Path>) -> Result<Vec<Option<String>>, AocError> {
+    let answers = read_input(path)?;
+    Ok(answers
+        .lines()
+        .map(|l| Some(l.trim().to_string()).filter(|a| !a.is_empty()))
+        .collect())
+}
+
+pub fn verify(
+    day: &Day,
+    input_path: impl AsRef<Path>,
+    answers_path: impl AsRef<Path>,
+) -> Result<Vec<PartResult>, AocError> {
+    let mut answers = load_answers(answers_path)?.into_iter();
+    let params = day.params(&params::load_sidecar(&input_path)?)?;
+    let (part1, part2) = day.run(&read_input(input_path)?, params.as_ref())?;
+
+    Ok(vec![
+        PartResult {
+            part: 1,
+            expected: answers.next().flatten(),
+            actual: part1,
+        },
+        PartResult {
+            part: 2,
+            expected: answers.next().flatten(),
+            actual: part2,
+        },
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn part_status() {
+        let result = |expected: Option<&str>, actual: &str| PartResult {
+            part: 1,
+            expected: expected.map(|s| s.to_string()),
+            actual: actual.to_string(),
+        };
+
+        assert_eq!(result(Some("PLPAFBCL"), "PLPAFBCL").status(), Status::Pass);
+        assert_eq!(result(Some("12560"), "12561").status(), Status::Fail);
+        assert_eq!(result(None, "12560").status(), Status::Missing);
+    }
+
+    #[test]
+    fn blank_answers() {
+        let dir = std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let answers = dir.join("01.answer");
+        fs::write(&answers, "24000\n\n").unwrap();
+
+        let day = crate::days::get(1).unwrap();
+        let results = verify(&day, "inputs/extra/01.sample", &answers).unwrap();
+        let statuses = results.iter().map(PartResult::status).collect::<Vec<_>>();
+        assert_eq!(statuses, [Status::Pass, Status::Missing]);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn day_cases() {
+        let day = crate::days::get(5).unwrap();
+        let cases = cases(&day);
+        let names = cases.iter().map(|c| c.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["input", "dataforce", "sample"]);
+        assert_eq!(
+            cases[2].answers_path,
+            PathBuf::from("inputs/extra/05.sample.answer")
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::commons::io::parse_str_lines;
+use crate::error::AocError;
+use crate::solution::Solution;
+
+pub struct Day{{NN}};
+
+impl Solution for Day{{NN}} {
+    type Input = Vec<String>;
+    type Params = ();
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, AocError> {
+        parse_str_lines(input)
+    }
+
+    fn part1(_input: &Vec<String>, _params: &()) -> Result<usize, AocError> {
+        Err(AocError::no_solution("part 1 not implemented yet"))
+    }
+
+    fn part2(_input: &Vec<String>, _params: &()) -> Result<usize, AocError> {
+        Err(AocError::no_solution("part 2 not implemented yet"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::solution_tests!(Day{{NN}}, "{{NN}}");
+}